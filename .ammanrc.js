@@ -22,6 +22,10 @@ module.exports = {
         label: "Pyth Solana USD feed",
         accountId: "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix",
       },
+      {
+        label: "Pyth USDC USD feed",
+        accountId: "5SSkXsEKQepHHAewytPVwdej4epN1nxgLVM84L4KXgy7",
+      },
       {
         label: "Raydium Pool admin",
         accountId: "adMCyoCgfkg7bQiJ9aBJ59H3BXLY3r5LNLfPpQfMzBe",
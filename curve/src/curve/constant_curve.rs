@@ -1,6 +1,6 @@
 use std::ops::{ Div, Mul, Sub };
 
-use crate::safe_number::safe_number::{ Math, NewSafeNumber, SafeNumber };
+use crate::safe_number::{ Math, SafeNumber };
 
 use super::{ CurveCalculator, TradeDirection };
 
@@ -41,7 +41,7 @@ impl CurveCalculator for ConstantCurveCalculator {
         let supply = self.get_bounding_curve_supply();
         let token_b_reserve_balance = self.get_token_b_reserve_balance();
 
-        f64::new(token_b_reserve_balance.div(supply))
+        SafeNumber::from(token_b_reserve_balance.div(supply))
     }
 
     fn calculate_amount_out(
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::safe_number::safe_number::SafeNumber;
+use crate::safe_number::SafeNumber;
 
 pub mod constant_curve;
 
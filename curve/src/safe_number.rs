@@ -19,10 +19,6 @@ pub trait Unwrap {
     fn unwrap(value: u128, percision: i32) -> Self;
 }
 
-pub trait NewSafeNumber {
-    fn new(value: Self) -> SafeNumber;
-}
-
 impl Unwrap for u64 {
     fn unwrap(value: u128, percision: i32) -> Self {
         f64::unwrap(value, percision).round() as u64
@@ -41,29 +37,29 @@ impl Unwrap for f64 {
     }
 }
 
-impl NewSafeNumber for f64 {
-    fn new(value: Self) -> SafeNumber {
+impl From<f64> for SafeNumber {
+    fn from(value: f64) -> Self {
         let percision = match value.to_string().split('.').nth(1) {
             Some(percision) => percision.len() as i32,
             None => 0,
         };
 
         SafeNumber {
-            value: SafeNumber::wrap(value.into(), percision),
+            value: SafeNumber::wrap(value, percision),
             percision,
         }
     }
 }
 
-impl NewSafeNumber for u64 {
-    fn new(value: Self) -> SafeNumber {
-        f64::new(value as f64)
+impl From<u64> for SafeNumber {
+    fn from(value: u64) -> Self {
+        SafeNumber::from(value as f64)
     }
 }
 
-impl NewSafeNumber for u128 {
-    fn new(value: Self) -> SafeNumber {
-        f64::new(value as f64)
+impl From<u128> for SafeNumber {
+    fn from(value: u128) -> Self {
+        SafeNumber::from(value as f64)
     }
 }
 
@@ -91,7 +87,7 @@ impl Math for SafeNumber {
     }
 
     fn inverse_div(&self, other: u128) -> SafeNumber {
-        f64::new((other as f64).div(self.unwrap::<f64>()))
+        SafeNumber::from((other as f64).div(self.unwrap::<f64>()))
     }
 }
 
@@ -1 +0,0 @@
-pub  mod safe_number;
\ No newline at end of file
@@ -1,11 +1,29 @@
 use anchor_lang::prelude::*;
 
+#[error_code]
+pub enum ConfigError {
+    #[msg("Basis points can't be greater than 10000")]
+    InvalidBasisPoints,
+}
+
 #[error_code]
 pub enum MintTokenError {
     #[msg("Liquidity percentage can't be less than 0 or greater than 100")]
     InvalidLiquidityPercentage,
     #[msg("Account not own by pyth oracle program")]
     InvalidFeedAccount,
+    #[msg("Launch protection is outside config bounds")]
+    InvalidLaunchProtection,
+    #[msg("Sell tax is greater than config maximum")]
+    InvalidSellTax,
+    #[msg("Presale requires a root, a duration and a wallet cap within the token cap")]
+    InvalidPresale,
+    #[msg("Vesting allocation, cliff or duration is invalid")]
+    InvalidVesting,
+    #[msg("Mint must sign or be the name, symbol and creator address")]
+    InvalidMint,
+    #[msg("Transfer fee requires a token 2022 mint and at most 100% basis points")]
+    InvalidTransferFee,
 }
 
 #[error_code]
@@ -16,6 +34,44 @@ pub enum SwapTokenError {
     NotTradeable,
     #[msg("Amount must be a value greater than zero")]
     InvalidAmount,
+    #[msg("Referral account does not match trade")]
+    InvalidReferral,
+    #[msg("Native settlement requires a native pair account")]
+    InvalidNativePair,
+    #[msg("Trader account is required during launch protection")]
+    MissingTraderAccount,
+    #[msg("Buy exceeds launch protection transaction cap")]
+    ProtectionTransactionCapExceeded,
+    #[msg("Buy exceeds launch protection wallet cap")]
+    ProtectionWalletCapExceeded,
+    #[msg("Can't sell in the same slot as a buy on this curve")]
+    SameSlotRoundTrip,
+    #[msg("Trading is paused by the circuit breaker")]
+    CircuitBreakerTripped,
+    #[msg("Route must trade between two different mints")]
+    InvalidRoute,
+    #[msg("Amount out is less than the minimum amount out")]
+    SlippageExceeded,
+    #[msg("Raydium pool accounts are required for migrated mints")]
+    MissingAmmAccounts,
+    #[msg("Raydium pool does not match the migrated pool")]
+    InvalidAmmPool,
+    #[msg("Raydium swap params are required for migrated mints")]
+    MissingAmmSwapParams,
+    #[msg("Mint uses a token extension zeroboost can't trade")]
+    UnsupportedMintExtension,
+    #[msg("Trading has not started for this mint")]
+    TradingNotStarted,
+    #[msg("Wallet is not on the presale allowlist")]
+    NotAllowlisted,
+    #[msg("Presale claim account is required during the presale")]
+    MissingPresaleClaimAccount,
+    #[msg("Buy exceeds presale wallet cap")]
+    PresaleWalletCapExceeded,
+    #[msg("Buy exceeds the presale token cap")]
+    PresaleCapExceeded,
+    #[msg("Raydium pool can't fill this amount")]
+    InsufficientAmmLiquidity,
 }
 
 #[error_code]
@@ -24,4 +80,48 @@ pub enum MigrateFundError {
     NotMigratable,
     #[msg("Mint already migrated")]
     AlreadyMigrated,
+    #[msg("Metaplex metadata accounts are required for legacy token mints")]
+    MissingMetadataAccount,
+}
+
+#[error_code]
+pub enum ClaimCreatorFeesError {
+    #[msg("No creator fees to claim")]
+    NothingToClaim,
+}
+
+#[error_code]
+pub enum ClaimVestedError {
+    #[msg("No vested tokens to claim")]
+    NothingToClaim,
+}
+
+#[error_code]
+pub enum CancelLaunchError {
+    #[msg("Launch can't be cancelled once traded")]
+    AlreadyTraded,
+    #[msg("Vesting escrow accounts are required to cancel this launch")]
+    MissingVestingAccounts,
+}
+
+#[error_code]
+pub enum UpdateMetadataError {
+    #[msg("Metadata can't be updated after migration")]
+    AlreadyMigrated,
+    #[msg("Metaplex metadata accounts are required for legacy token mints")]
+    MissingMetadataAccount,
+}
+
+#[error_code]
+pub enum BuybackError {
+    #[msg("Buyback vault is empty")]
+    NothingToBuyback,
+    #[msg("Only the creator or the admin can run the buyback")]
+    InvalidAuthority,
+}
+
+#[error_code]
+pub enum LimitOrderError {
+    #[msg("Curve price has not reached the order limit")]
+    LimitNotReached,
 }
@@ -10,6 +10,7 @@ pub struct MintEvent {
     pub decimals: u8,
     pub bounding_curve: Pubkey,
     pub creator: Pubkey,
+    pub authorities_revoked: bool,
     pub timestamp: i64,
 }
 
@@ -26,6 +27,13 @@ pub struct SwapEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TradingStartEvent {
+    pub mint: Pubkey,
+    pub trading_starts_at: i64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MigrateTriggerEvent {
     pub mint: Pubkey,
@@ -35,5 +43,109 @@ pub struct MigrateTriggerEvent {
 #[event]
 pub struct MigrateEvent {
     pub mint: Pubkey,
+    pub mint_authority_revoked: bool,
+    pub freeze_authority_revoked: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimCreatorFeesEvent {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerEvent {
+    pub mint: Pubkey,
+    pub window_start_pair_balance: u64,
+    pub virtual_pair_balance: u64,
+    pub paused_until_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LimitOrderPlacedEvent {
+    pub mint: Pubkey,
+    pub limit_order: Pubkey,
+    pub owner: Pubkey,
+    pub trade_direction: u8,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LimitOrderCancelledEvent {
+    pub mint: Pubkey,
+    pub limit_order: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LimitOrderFilledEvent {
+    pub mint: Pubkey,
+    pub limit_order: Pubkey,
+    pub owner: Pubkey,
+    pub keeper: Pubkey,
+    pub amount_out: u64,
+    pub keeper_tip: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SellTaxEvent {
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackBurnEvent {
+    pub mint: Pubkey,
+    pub pair_amount: u64,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingEvent {
+    pub mint: Pubkey,
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub start_at: i64,
+    pub cliff_at: i64,
+    pub end_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimVestedEvent {
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CancelLaunchEvent {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub burned_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UpdateMetadataEvent {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
     pub timestamp: i64,
 }
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{
+        burn,
+        transfer_checked,
+        Burn,
+        Mint,
+        TokenAccount,
+        TokenInterface,
+        TransferChecked,
+    },
+};
+use curve::{
+    curve::{ constant_curve::ConstantCurveCalculator, CurveCalculator, TradeDirection },
+    safe_number::SafeNumber,
+};
+use raydium_cp_swap::{ cpi::{ accounts::Swap as AmmSwap, swap_base_input }, program::RaydiumCpSwap };
+
+use crate::{
+    admin,
+    error::{ BuybackError, SwapTokenError },
+    events::BuybackBurnEvent,
+    instructions::{
+        check_circuit_breaker,
+        enforce_trading_start,
+        trigger_migration,
+        update_circuit_breaker,
+    },
+    states::{ bounding_curve::BoundingCurve, config::Config },
+    utils::{ get_amm_pool_state_address, token_account_amount },
+    BUYBACK_VAULT_SEED,
+    CONFIG_SEED,
+    CURVE_RESERVE_SEED,
+    CURVE_SEED,
+};
+
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(mut, address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = bounding_curve.pair)]
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()], bump)]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    config: Box<Account<'info, Config>>,
+    #[account(seeds = [bounding_curve.key().as_ref(), CURVE_RESERVE_SEED.as_bytes()], bump)]
+    /// CHECK: bounding curve extra layer account for token reserve
+    bounding_curve_reserve: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounding_curve_reserve,
+        associated_token::token_program = mint_token_program
+    )]
+    bounding_curve_reserve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pair,
+        associated_token::authority = bounding_curve_reserve
+    )]
+    bounding_curve_reserve_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), BUYBACK_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    buyback_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: raydium cp pool accounts, only required once the curve migrated
+    amm_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by raydium
+    amm_authority: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated against the pool created on migration
+    amm_pool_state: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated by raydium
+    amm_mint_vault: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated by raydium
+    amm_pair_vault: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated by raydium
+    amm_observable_state: Option<UncheckedAccount<'info>>,
+    amm_program: Option<Program<'info, RaydiumCpSwap>>,
+    // Only the creator or the admin sets the slippage bound, anyone else could pass zero and
+    // sandwich the buyback
+    #[account(
+        constraint = authority.key() == bounding_curve.creator ||
+        authority.key() == admin::ID @ BuybackError::InvalidAuthority
+    )]
+    authority: Signer<'info>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct BuybackAndBurnParams {
+    minimum_amount_out: u64,
+}
+
+impl<'info> BuybackAndBurn<'info> {
+    pub fn process_buyback_and_burn(
+        context: Context<BuybackAndBurn>,
+        params: &BuybackAndBurnParams
+    ) -> Result<()> {
+        let accounts = &mut *context.accounts;
+        let amount = accounts.buyback_vault.amount;
+
+        if amount == 0 {
+            return err!(BuybackError::NothingToBuyback);
+        }
+
+        let bounding_curve_key = accounts.bounding_curve.key();
+        let signer_seeds = &[
+            bounding_curve_key.as_ref(),
+            CURVE_RESERVE_SEED.as_bytes(),
+            &[context.bumps.bounding_curve_reserve],
+        ];
+        let signer_seeds = &[&signer_seeds[..]];
+
+        // Bought tokens land in the reserve token account and are burned from there
+        let (pair_amount, token_amount) = match accounts.bounding_curve.migrated {
+            true => accounts.buyback_from_amm(amount, params.minimum_amount_out, signer_seeds)?,
+            false => accounts.buyback_from_curve(amount, params.minimum_amount_out, signer_seeds)?,
+        };
+
+        burn(
+            CpiContext::new_with_signer(
+                accounts.mint_token_program.to_account_info(),
+                Burn {
+                    mint: accounts.mint.to_account_info(),
+                    from: accounts.bounding_curve_reserve_ata.to_account_info(),
+                    authority: accounts.bounding_curve_reserve.to_account_info(),
+                },
+                signer_seeds
+            ),
+            token_amount
+        )?;
+
+        let clock = Clock::get()?;
+
+        emit!(BuybackBurnEvent {
+            pair_amount,
+            token_amount,
+            mint: accounts.mint.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn buyback_from_curve(
+        &mut self,
+        amount: u64,
+        minimum_amount_out: u64,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<(u64, u64)> {
+        if !self.bounding_curve.tradeable {
+            return err!(SwapTokenError::NotTradeable);
+        }
+
+        let clock = Clock::get()?;
+        enforce_trading_start(&self.bounding_curve, clock.unix_timestamp)?;
+        check_circuit_breaker(&self.config, &mut self.bounding_curve, clock.slot)?;
+
+        let initial_price = SafeNumber::from(self.bounding_curve.initial_price);
+
+        let amount_out = ConstantCurveCalculator::calculate_amount_out(
+            initial_price,
+            amount,
+            TradeDirection::BtoA
+        ).min(self.bounding_curve.virtual_token_balance);
+
+        if amount_out < minimum_amount_out {
+            return err!(SwapTokenError::SlippageExceeded);
+        }
+
+        let amount_in = ConstantCurveCalculator::calculate_amount_out(
+            initial_price,
+            amount_out,
+            TradeDirection::AtoB
+        );
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    mint: self.pair.to_account_info(),
+                    from: self.buyback_vault.to_account_info(),
+                    to: self.bounding_curve_reserve_pair_ata.to_account_info(),
+                    authority: self.bounding_curve_reserve.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount_in,
+            self.pair.decimals
+        )?;
+
+        self.bounding_curve.add(self.pair.key(), amount_in);
+        self.bounding_curve.sub(self.mint.key(), amount_out);
+        self.bounding_curve.traded = true;
+
+        update_circuit_breaker(&self.config, &mut self.bounding_curve, &clock);
+
+        self.bounding_curve_reserve_pair_ata.reload()?;
+        trigger_migration(
+            &mut self.bounding_curve,
+            self.bounding_curve_reserve_pair_ata.amount,
+            clock.unix_timestamp
+        );
+
+        Ok((amount_in, amount_out))
+    }
+
+    fn buyback_from_amm(
+        &self,
+        amount: u64,
+        minimum_amount_out: u64,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<(u64, u64)> {
+        let (
+            Some(amm_config),
+            Some(amm_authority),
+            Some(amm_pool_state),
+            Some(amm_mint_vault),
+            Some(amm_pair_vault),
+            Some(amm_observable_state),
+            Some(amm_program),
+        ) = (
+            &self.amm_config,
+            &self.amm_authority,
+            &self.amm_pool_state,
+            &self.amm_mint_vault,
+            &self.amm_pair_vault,
+            &self.amm_observable_state,
+            &self.amm_program,
+        ) else {
+            return err!(SwapTokenError::MissingAmmAccounts);
+        };
+
+        let amm_pool_state_address = get_amm_pool_state_address(
+            &amm_config.key(),
+            self.mint.key(),
+            self.pair.key(),
+            &amm_program.key()
+        );
+
+        if amm_pool_state.key() != amm_pool_state_address {
+            return err!(SwapTokenError::InvalidAmmPool);
+        }
+
+        let token_balance = self.bounding_curve_reserve_ata.amount;
+
+        swap_base_input(
+            CpiContext::new_with_signer(
+                amm_program.to_account_info(),
+                AmmSwap {
+                    payer: self.bounding_curve_reserve.to_account_info(),
+                    authority: amm_authority.to_account_info(),
+                    amm_config: amm_config.to_account_info(),
+                    pool_state: amm_pool_state.to_account_info(),
+                    input_token_account: self.buyback_vault.to_account_info(),
+                    output_token_account: self.bounding_curve_reserve_ata.to_account_info(),
+                    input_vault: amm_pair_vault.to_account_info(),
+                    output_vault: amm_mint_vault.to_account_info(),
+                    input_token_program: self.token_program.to_account_info(),
+                    output_token_program: self.mint_token_program.to_account_info(),
+                    input_token_mint: self.pair.to_account_info(),
+                    output_token_mint: self.mint.to_account_info(),
+                    observation_state: amm_observable_state.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            minimum_amount_out
+        )?;
+
+        let token_amount =
+            token_account_amount(&self.bounding_curve_reserve_ata.to_account_info())? -
+            token_balance;
+
+        Ok((amount, token_amount))
+    }
+}
@@ -0,0 +1,273 @@
+use anchor_lang::{ prelude::*, system_program::{ self, transfer, Transfer } };
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{
+        burn,
+        close_account,
+        transfer_checked,
+        Burn,
+        CloseAccount,
+        Mint,
+        TokenAccount,
+        TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    error::CancelLaunchError,
+    events::CancelLaunchEvent,
+    states::{ bounding_curve::BoundingCurve, vesting::Vesting },
+    BUYBACK_VAULT_SEED,
+    CREATOR_FEE_VAULT_SEED,
+    CURVE_RESERVE_SEED,
+    CURVE_SEED,
+    VESTING_SEED,
+};
+
+#[derive(Accounts)]
+pub struct CancelLaunch<'info> {
+    #[account(mut, address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = bounding_curve.pair)]
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()],
+        bump,
+        has_one = creator,
+        close = creator
+    )]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounding_curve,
+        associated_token::token_program = mint_token_program
+    )]
+    bounding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [bounding_curve.key().as_ref(), CURVE_RESERVE_SEED.as_bytes()], bump)]
+    /// CHECK: bounding curve extra layer account for token reserve
+    bounding_curve_reserve: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounding_curve_reserve,
+        associated_token::token_program = mint_token_program
+    )]
+    bounding_curve_reserve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pair,
+        associated_token::authority = bounding_curve_reserve
+    )]
+    bounding_curve_reserve_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), CREATOR_FEE_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    creator_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), BUYBACK_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    buyback_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [bounding_curve.key().as_ref(), VESTING_SEED.as_bytes()], bump)]
+    /// CHECK: always passed so a launch with a vesting escrow can't be cancelled without burning it
+    vesting: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = mint_token_program
+    )]
+    vesting_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    creator: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = pair,
+        associated_token::authority = creator
+    )]
+    creator_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    system_program: Program<'info, System>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> CancelLaunch<'info> {
+    pub fn process_cancel_launch(context: Context<CancelLaunch>) -> Result<()> {
+        let accounts = &context.accounts;
+
+        if !accounts.bounding_curve.is_untraded() {
+            return err!(CancelLaunchError::AlreadyTraded);
+        }
+
+        let mint_key = accounts.mint.key();
+        let bounding_curve_key = accounts.bounding_curve.key();
+        let curve_signer_seeds = &[
+            mint_key.as_ref(),
+            CURVE_SEED.as_bytes(),
+            &[context.bumps.bounding_curve],
+        ];
+        let reserve_signer_seeds = &[
+            bounding_curve_key.as_ref(),
+            CURVE_RESERVE_SEED.as_bytes(),
+            &[context.bumps.bounding_curve_reserve],
+        ];
+
+        let mut burned_amount = accounts.burn_and_close(
+            &accounts.bounding_curve_ata,
+            accounts.bounding_curve.to_account_info(),
+            &[&curve_signer_seeds[..]]
+        )?;
+        burned_amount += accounts.burn_and_close(
+            &accounts.bounding_curve_reserve_ata,
+            accounts.bounding_curve_reserve.to_account_info(),
+            &[&reserve_signer_seeds[..]]
+        )?;
+
+        if !accounts.vesting.data_is_empty() {
+            let vesting = Vesting::try_deserialize(&mut &accounts.vesting.try_borrow_data()?[..])?;
+            let vesting_ata = accounts.vesting_ata
+                .as_ref()
+                .ok_or(CancelLaunchError::MissingVestingAccounts)?;
+            let vesting_signer_seeds = &[
+                bounding_curve_key.as_ref(),
+                VESTING_SEED.as_bytes(),
+                &[vesting.bump],
+            ];
+
+            burned_amount += accounts.burn_and_close(
+                vesting_ata,
+                accounts.vesting.to_account_info(),
+                &[&vesting_signer_seeds[..]]
+            )?;
+            accounts.close_vesting()?;
+        }
+
+        // Pair tokens donated to an untraded curve go back to the creator so the accounts can close
+        for account in [
+            &accounts.bounding_curve_reserve_pair_ata,
+            &accounts.creator_fee_vault,
+            &accounts.buyback_vault,
+        ] {
+            accounts.sweep_and_close(account, &[&reserve_signer_seeds[..]])?;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                accounts.system_program.to_account_info(),
+                Transfer {
+                    from: accounts.bounding_curve_reserve.to_account_info(),
+                    to: accounts.creator.to_account_info(),
+                },
+                &[&reserve_signer_seeds[..]]
+            ),
+            accounts.bounding_curve_reserve.lamports()
+        )?;
+
+        let clock = Clock::get()?;
+
+        emit!(CancelLaunchEvent {
+            burned_amount,
+            mint: mint_key,
+            creator: accounts.creator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn close_vesting(&self) -> Result<()> {
+        let vesting = self.vesting.to_account_info();
+        let creator = self.creator.to_account_info();
+
+        **creator.try_borrow_mut_lamports()? += vesting.lamports();
+        **vesting.try_borrow_mut_lamports()? = 0;
+        vesting.realloc(0, false)?;
+        vesting.assign(&system_program::ID);
+
+        Ok(())
+    }
+
+    fn sweep_and_close(
+        &self,
+        account: &InterfaceAccount<'info, TokenAccount>,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<()> {
+        let amount = account.amount;
+
+        if amount > 0 {
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        mint: self.pair.to_account_info(),
+                        from: account.to_account_info(),
+                        to: self.creator_pair_ata.to_account_info(),
+                        authority: self.bounding_curve_reserve.to_account_info(),
+                    },
+                    signer_seeds
+                ),
+                amount,
+                self.pair.decimals
+            )?;
+        }
+
+        close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: account.to_account_info(),
+                    destination: self.creator.to_account_info(),
+                    authority: self.bounding_curve_reserve.to_account_info(),
+                },
+                signer_seeds
+            )
+        )
+    }
+
+    fn burn_and_close(
+        &self,
+        account: &InterfaceAccount<'info, TokenAccount>,
+        authority: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<u64> {
+        let amount = account.amount;
+
+        if amount > 0 {
+            burn(
+                CpiContext::new_with_signer(
+                    self.mint_token_program.to_account_info(),
+                    Burn {
+                        mint: self.mint.to_account_info(),
+                        from: account.to_account_info(),
+                        authority: authority.clone(),
+                    },
+                    signer_seeds
+                ),
+                amount
+            )?;
+        }
+
+        close_account(
+            CpiContext::new_with_signer(
+                self.mint_token_program.to_account_info(),
+                CloseAccount {
+                    authority,
+                    account: account.to_account_info(),
+                    destination: self.creator.to_account_info(),
+                },
+                signer_seeds
+            )
+        )?;
+
+        Ok(amount)
+    }
+}
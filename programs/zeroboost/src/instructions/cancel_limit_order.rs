@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{
+        close_account,
+        transfer_checked,
+        CloseAccount,
+        Mint,
+        TokenAccount,
+        TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    events::LimitOrderCancelledEvent,
+    states::{ limit_order::LimitOrder, trader::Trader },
+    LIMIT_ORDER_SEED,
+    TRADER_SEED,
+};
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    #[account(address = limit_order.bounding_curve)]
+    /// CHECK: bounding curve the order trades on
+    bounding_curve: UncheckedAccount<'info>,
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [
+            bounding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            limit_order.id.to_le_bytes().as_ref(),
+            LIMIT_ORDER_SEED.as_bytes(),
+        ],
+        bump = limit_order.bump,
+        has_one = owner,
+        close = owner
+    )]
+    limit_order: Box<Account<'info, LimitOrder>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = limit_order,
+        associated_token::token_program = mint_token_program
+    )]
+    limit_order_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pair,
+        associated_token::authority = limit_order
+    )]
+    limit_order_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), limit_order.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump,
+        close = owner
+    )]
+    limit_order_trader: Box<Account<'info, Trader>>,
+    #[account(mut)]
+    owner: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = mint_token_program
+    )]
+    owner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pair,
+        associated_token::authority = owner
+    )]
+    owner_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Returns any escrow balance to the order owner and closes the escrow account
+pub fn close_limit_order_escrow<'info>(
+    token_program: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    owner: AccountInfo<'info>,
+    limit_order: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<()> {
+    if escrow.amount > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TransferChecked {
+                    mint: mint.to_account_info(),
+                    from: escrow.to_account_info(),
+                    to: destination,
+                    authority: limit_order.clone(),
+                },
+                signer_seeds
+            ),
+            escrow.amount,
+            mint.decimals
+        )?;
+    }
+
+    close_account(
+        CpiContext::new_with_signer(
+            token_program,
+            CloseAccount {
+                account: escrow.to_account_info(),
+                destination: owner,
+                authority: limit_order,
+            },
+            signer_seeds
+        )
+    )
+}
+
+impl<'info> CancelLimitOrder<'info> {
+    pub fn process_cancel_limit_order(context: Context<CancelLimitOrder>) -> Result<()> {
+        let accounts = &context.accounts;
+        let limit_order = &accounts.limit_order;
+
+        let bounding_curve_key = accounts.bounding_curve.key();
+        let owner_key = accounts.owner.key();
+        let id = limit_order.id.to_le_bytes();
+        let signer_seeds = &[
+            bounding_curve_key.as_ref(),
+            owner_key.as_ref(),
+            id.as_ref(),
+            LIMIT_ORDER_SEED.as_bytes(),
+            &[limit_order.bump],
+        ];
+        let signer_seeds = &[&signer_seeds[..]];
+
+        close_limit_order_escrow(
+            accounts.mint_token_program.to_account_info(),
+            &accounts.mint,
+            &accounts.limit_order_ata,
+            accounts.owner_ata.to_account_info(),
+            accounts.owner.to_account_info(),
+            limit_order.to_account_info(),
+            signer_seeds
+        )?;
+
+        close_limit_order_escrow(
+            accounts.token_program.to_account_info(),
+            &accounts.pair,
+            &accounts.limit_order_pair_ata,
+            accounts.owner_pair_ata.to_account_info(),
+            accounts.owner.to_account_info(),
+            limit_order.to_account_info(),
+            signer_seeds
+        )?;
+
+        let clock = Clock::get()?;
+
+        emit!(LimitOrderCancelledEvent {
+            mint: accounts.mint.key(),
+            limit_order: limit_order.key(),
+            owner: owner_key,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
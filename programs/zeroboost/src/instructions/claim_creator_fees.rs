@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{ transfer_checked, Mint, TokenAccount, TransferChecked },
+};
+
+use crate::{
+    error::ClaimCreatorFeesError,
+    events::ClaimCreatorFeesEvent,
+    states::bounding_curve::BoundingCurve,
+    CREATOR_FEE_VAULT_SEED,
+    CURVE_RESERVE_SEED,
+    CURVE_SEED,
+};
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = bounding_curve.pair)]
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()],
+        bump,
+        has_one = creator
+    )]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    #[account(seeds = [bounding_curve.key().as_ref(), CURVE_RESERVE_SEED.as_bytes()], bump)]
+    /// CHECK: bounding curve extra layer account for token reserve
+    bounding_curve_reserve: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), CREATOR_FEE_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    creator_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    creator: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = pair,
+        associated_token::authority = creator
+    )]
+    creator_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimCreatorFees<'info> {
+    pub fn process_claim_creator_fees(context: Context<ClaimCreatorFees>) -> Result<()> {
+        let accounts = &context.accounts;
+        let amount = accounts.creator_fee_vault.amount;
+
+        if amount == 0 {
+            return err!(ClaimCreatorFeesError::NothingToClaim);
+        }
+
+        let bounding_curve_key = accounts.bounding_curve.key();
+        let signer_seeds = &[
+            bounding_curve_key.as_ref(),
+            CURVE_RESERVE_SEED.as_bytes(),
+            &[context.bumps.bounding_curve_reserve],
+        ];
+        let signer_seeds = &[&signer_seeds[..]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    mint: accounts.pair.to_account_info(),
+                    from: accounts.creator_fee_vault.to_account_info(),
+                    to: accounts.creator_pair_ata.to_account_info(),
+                    authority: accounts.bounding_curve_reserve.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            accounts.pair.decimals
+        )?;
+
+        let clock = Clock::get()?;
+
+        emit!(ClaimCreatorFeesEvent {
+            amount,
+            mint: accounts.mint.key(),
+            creator: accounts.creator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
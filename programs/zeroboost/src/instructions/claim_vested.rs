@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{ transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked },
+};
+
+use crate::{
+    error::ClaimVestedError,
+    events::ClaimVestedEvent,
+    states::{ bounding_curve::BoundingCurve, vesting::Vesting },
+    CURVE_SEED,
+    VESTING_SEED,
+};
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()], bump)]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), VESTING_SEED.as_bytes()],
+        bump = vesting.bump,
+        has_one = beneficiary
+    )]
+    vesting: Box<Account<'info, Vesting>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = mint_token_program
+    )]
+    vesting_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    beneficiary: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = mint_token_program
+    )]
+    beneficiary_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    system_program: Program<'info, System>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn process_claim_vested(context: Context<ClaimVested>) -> Result<()> {
+        let accounts = &mut *context.accounts;
+        let clock = Clock::get()?;
+
+        let vesting = &accounts.vesting;
+        let amount = vesting.vested_amount(clock.unix_timestamp) - vesting.claimed_amount;
+
+        if amount == 0 {
+            return err!(ClaimVestedError::NothingToClaim);
+        }
+
+        let bounding_curve_key = accounts.bounding_curve.key();
+        let signer_seeds = &[bounding_curve_key.as_ref(), VESTING_SEED.as_bytes(), &[vesting.bump]];
+        let signer_seeds = &[&signer_seeds[..]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.mint_token_program.to_account_info(),
+                TransferChecked {
+                    mint: accounts.mint.to_account_info(),
+                    from: accounts.vesting_ata.to_account_info(),
+                    to: accounts.beneficiary_ata.to_account_info(),
+                    authority: vesting.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            accounts.mint.decimals
+        )?;
+
+        let vesting = &mut accounts.vesting;
+        vesting.claimed_amount += amount;
+
+        emit!(ClaimVestedEvent {
+            amount,
+            mint: accounts.mint.key(),
+            beneficiary: vesting.beneficiary,
+            claimed_amount: vesting.claimed_amount,
+            total_amount: vesting.total_amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
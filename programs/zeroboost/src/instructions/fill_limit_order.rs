@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ spl_token::native_mint, Token },
+    token_interface::{ Mint, TokenAccount, TokenInterface },
+};
+
+use crate::{
+    error::LimitOrderError,
+    events::LimitOrderFilledEvent,
+    instructions::{
+        close_limit_order_escrow,
+        enforce_launch_protection,
+        enforce_same_slot_sell_restriction,
+        invoke_swap,
+        record_buy_slot,
+        SwapParams,
+        SwapResult,
+    },
+    program::Zeroboost,
+    states::{ bounding_curve::BoundingCurve, limit_order::LimitOrder, trader::Trader },
+    utils::get_transfer_amount_with_fee,
+    LIMIT_ORDER_SEED,
+    TRADER_SEED,
+};
+
+#[derive(Accounts)]
+pub struct FillLimitOrder<'info> {
+    #[account(address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = bounding_curve.pair)]
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: validated by swap
+    config: UncheckedAccount<'info>,
+    #[account(mut, address = limit_order.bounding_curve)]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    /// CHECK: validated by swap
+    bounding_curve_reserve: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    bounding_curve_reserve_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    bounding_curve_reserve_pair_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    creator_fee_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    protocol_fee_pair_ata: UncheckedAccount<'info>,
+    /// CHECK: validated by swap
+    buyback_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            bounding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            limit_order.id.to_le_bytes().as_ref(),
+            LIMIT_ORDER_SEED.as_bytes(),
+        ],
+        bump = limit_order.bump,
+        has_one = owner,
+        close = owner
+    )]
+    limit_order: Box<Account<'info, LimitOrder>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = limit_order,
+        associated_token::token_program = mint_token_program
+    )]
+    limit_order_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pair,
+        associated_token::authority = limit_order
+    )]
+    limit_order_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), limit_order.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump,
+        close = owner
+    )]
+    limit_order_trader: Box<Account<'info, Trader>>,
+    // Launch protection and same slot sells are tracked per owner, so fills count against the
+    // same record as the owner's direct swaps
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), owner.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump
+    )]
+    owner_trader: Box<Account<'info, Trader>>,
+    #[account(mut)]
+    /// CHECK: order owner, receives fill proceeds and rent
+    owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = mint_token_program
+    )]
+    owner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = pair,
+        associated_token::authority = owner
+    )]
+    owner_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    keeper: Signer<'info>,
+    zeroboost_program: Program<'info, Zeroboost>,
+    system_program: Program<'info, System>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> FillLimitOrder<'info> {
+    pub fn process_fill_limit_order(context: Context<FillLimitOrder>) -> Result<()> {
+        let accounts = &mut *context.accounts;
+
+        let bounding_curve_key = accounts.bounding_curve.key();
+        let owner_key = accounts.owner.key();
+        let id = accounts.limit_order.id.to_le_bytes();
+        let signer_seeds = &[
+            bounding_curve_key.as_ref(),
+            owner_key.as_ref(),
+            id.as_ref(),
+            LIMIT_ORDER_SEED.as_bytes(),
+            &[accounts.limit_order.bump],
+        ];
+        let signer_seeds = &[&signer_seeds[..]];
+
+        let clock = Clock::get()?;
+        let trade_direction = accounts.limit_order.trade_direction;
+
+        if trade_direction == 1 {
+            enforce_same_slot_sell_restriction(
+                &accounts.bounding_curve,
+                Some(&accounts.owner_trader),
+                clock.slot
+            )?;
+        }
+
+        let result = accounts.execute_order(signer_seeds)?;
+
+        // Swap wrote the curve, the cached copy is refreshed so it isn't written back stale
+        accounts.bounding_curve.reload()?;
+
+        if trade_direction == 0 {
+            let amount_out = get_transfer_amount_with_fee(
+                &accounts.mint.to_account_info(),
+                result.token_amount
+            )?;
+
+            enforce_launch_protection(
+                &accounts.bounding_curve,
+                Some(&mut accounts.owner_trader),
+                amount_out,
+                clock.unix_timestamp
+            )?;
+            record_buy_slot(
+                &accounts.bounding_curve,
+                Some(&mut accounts.owner_trader),
+                clock.slot
+            )?;
+        }
+
+        let amount_out = match trade_direction {
+            0 => result.token_amount,
+            _ => result.pair_amount,
+        };
+
+        if amount_out < accounts.limit_order.minimum_amount_out {
+            return err!(LimitOrderError::LimitNotReached);
+        }
+
+        accounts.limit_order_ata.reload()?;
+        accounts.limit_order_pair_ata.reload()?;
+
+        close_limit_order_escrow(
+            accounts.mint_token_program.to_account_info(),
+            &accounts.mint,
+            &accounts.limit_order_ata,
+            accounts.owner_ata.to_account_info(),
+            accounts.owner.to_account_info(),
+            accounts.limit_order.to_account_info(),
+            signer_seeds
+        )?;
+
+        close_limit_order_escrow(
+            accounts.token_program.to_account_info(),
+            &accounts.pair,
+            &accounts.limit_order_pair_ata,
+            accounts.owner_pair_ata.to_account_info(),
+            accounts.owner.to_account_info(),
+            accounts.limit_order.to_account_info(),
+            signer_seeds
+        )?;
+
+        // Keeper tip is carved out of the order lamports, the rest is refunded to the owner on close
+        let keeper_tip = accounts.limit_order.keeper_tip;
+        **accounts.limit_order.to_account_info().try_borrow_mut_lamports()? -= keeper_tip;
+        **accounts.keeper.to_account_info().try_borrow_mut_lamports()? += keeper_tip;
+
+        emit!(LimitOrderFilledEvent {
+            amount_out,
+            keeper_tip,
+            mint: accounts.mint.key(),
+            limit_order: accounts.limit_order.key(),
+            owner: owner_key,
+            keeper: accounts.keeper.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Executes the order through swap with the order account as the trader
+    fn execute_order(&self, signer_seeds: &[&[&[u8]]]) -> Result<SwapResult> {
+        let limit_order = &self.limit_order;
+
+        // Buys on a native pair spend the wrapped escrow instead of lamports
+        let native = match limit_order.trade_direction {
+            0 if self.pair.key() == native_mint::ID => Some(false),
+            _ => None,
+        };
+
+        let swap_accounts = crate::accounts::Swap {
+            mint: self.mint.key(),
+            pair: self.pair.key(),
+            config: self.config.key(),
+            bounding_curve: self.bounding_curve.key(),
+            bounding_curve_reserve: self.bounding_curve_reserve.key(),
+            bounding_curve_reserve_ata: self.bounding_curve_reserve_ata.key(),
+            bounding_curve_reserve_pair_ata: self.bounding_curve_reserve_pair_ata.key(),
+            creator_fee_vault: self.creator_fee_vault.key(),
+            protocol_fee_pair_ata: self.protocol_fee_pair_ata.key(),
+            buyback_vault: self.buyback_vault.key(),
+            referral: None,
+            referrer_pair_ata: None,
+            trader: Some(self.limit_order_trader.key()),
+            presale_claim: None,
+            payer: limit_order.key(),
+            payer_ata: self.limit_order_ata.key(),
+            payer_pair_ata: self.limit_order_pair_ata.key(),
+            payer_native_pair: None,
+            amm_config: None,
+            amm_authority: None,
+            amm_pool_state: None,
+            amm_mint_vault: None,
+            amm_pair_vault: None,
+            amm_observable_state: None,
+            amm_program: None,
+            system_program: self.system_program.key(),
+            mint_token_program: self.mint_token_program.key(),
+            token_program: self.token_program.key(),
+            associated_token_program: self.associated_token_program.key(),
+        };
+
+        invoke_swap(
+            swap_accounts,
+            &[
+                self.mint.to_account_info(),
+                self.pair.to_account_info(),
+                self.config.to_account_info(),
+                self.bounding_curve.to_account_info(),
+                self.bounding_curve_reserve.to_account_info(),
+                self.bounding_curve_reserve_ata.to_account_info(),
+                self.bounding_curve_reserve_pair_ata.to_account_info(),
+                self.creator_fee_vault.to_account_info(),
+                self.protocol_fee_pair_ata.to_account_info(),
+                self.buyback_vault.to_account_info(),
+                self.limit_order_trader.to_account_info(),
+                limit_order.to_account_info(),
+                self.limit_order_ata.to_account_info(),
+                self.limit_order_pair_ata.to_account_info(),
+                self.zeroboost_program.to_account_info(),
+                self.system_program.to_account_info(),
+                self.mint_token_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.associated_token_program.to_account_info(),
+            ],
+            SwapParams::new(limit_order.amount_in, limit_order.trade_direction, native),
+            signer_seeds
+        )
+    }
+}
@@ -1,6 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::{ admin, states::config::{ Config, CONFIG_SIZE }, CONFIG_SEED };
+use crate::{
+    admin,
+    error::ConfigError,
+    states::config::{ Config, CONFIG_SIZE },
+    utils::Validate,
+    BASIS_POINTS_DIVISOR,
+    CONFIG_SEED,
+};
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
@@ -24,6 +31,39 @@ pub struct InitializeConfigParams {
     minimum_curve_usd_valuation: u16,
     maximum_curve_usd_valuation: u16,
     estimated_raydium_cp_pool_fee: u64,
+    swap_fee_basis_points: u16,
+    creator_fee_share_basis_points: u16,
+    referral_fee_share_basis_points: u16,
+    maximum_protection_duration: u32,
+    minimum_protection_cap_basis_points: u16,
+    circuit_breaker_basis_points: u16,
+    circuit_breaker_window_slots: u64,
+    circuit_breaker_cooldown_slots: u64,
+    maximum_swap_fee_basis_points: u16,
+    volatility_reference_basis_points: u16,
+    volatility_half_life: u32,
+    limit_order_keeper_tip: u64,
+    maximum_sell_tax_basis_points: u16,
+}
+
+impl Validate for InitializeConfigParams {
+    fn validate(&self) -> Result<()> {
+        if
+            (self.swap_fee_basis_points as u64) > BASIS_POINTS_DIVISOR ||
+            (self.creator_fee_share_basis_points as u64) +
+                (self.referral_fee_share_basis_points as u64) > BASIS_POINTS_DIVISOR ||
+            (self.minimum_protection_cap_basis_points as u64) > BASIS_POINTS_DIVISOR ||
+            (self.circuit_breaker_basis_points as u64) > BASIS_POINTS_DIVISOR ||
+            (self.maximum_swap_fee_basis_points as u64) > BASIS_POINTS_DIVISOR ||
+            // Sell tax is taken from proceeds alongside the swap fee so both must fit together
+            (self.maximum_sell_tax_basis_points as u64) +
+                (self.swap_fee_basis_points.max(self.maximum_swap_fee_basis_points) as u64) >
+                BASIS_POINTS_DIVISOR
+        {
+            return err!(ConfigError::InvalidBasisPoints);
+        }
+        Ok(())
+    }
 }
 
 impl<'info> InitializeConfig<'info> {
@@ -31,6 +71,7 @@ impl<'info> InitializeConfig<'info> {
         context: Context<InitializeConfig>,
         params: InitializeConfigParams
     ) -> Result<()> {
+        params.validate()?;
         let config = &mut context.accounts.config;
 
         config.bump = context.bumps.config;
@@ -39,6 +80,19 @@ impl<'info> InitializeConfig<'info> {
         config.minimum_curve_usd_valuation = params.minimum_curve_usd_valuation;
         config.maximum_curve_usd_valuation = params.maximum_curve_usd_valuation;
         config.estimated_raydium_cp_pool_creation_fee = params.estimated_raydium_cp_pool_fee;
+        config.swap_fee_basis_points = params.swap_fee_basis_points;
+        config.creator_fee_share_basis_points = params.creator_fee_share_basis_points;
+        config.referral_fee_share_basis_points = params.referral_fee_share_basis_points;
+        config.maximum_protection_duration = params.maximum_protection_duration;
+        config.minimum_protection_cap_basis_points = params.minimum_protection_cap_basis_points;
+        config.circuit_breaker_basis_points = params.circuit_breaker_basis_points;
+        config.circuit_breaker_window_slots = params.circuit_breaker_window_slots;
+        config.circuit_breaker_cooldown_slots = params.circuit_breaker_cooldown_slots;
+        config.maximum_swap_fee_basis_points = params.maximum_swap_fee_basis_points;
+        config.volatility_reference_basis_points = params.volatility_reference_basis_points;
+        config.volatility_half_life = params.volatility_half_life;
+        config.limit_order_keeper_tip = params.limit_order_keeper_tip;
+        config.maximum_sell_tax_basis_points = params.maximum_sell_tax_basis_points;
 
         Ok(())
     }
@@ -1,11 +1,15 @@
 use std::ops::{Div, Mul};
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::system_program::{transfer, Transfer};
-use anchor_spl::token::{transfer_checked, TransferChecked};
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{burn, Burn, Mint, Token, TokenAccount},
+    metadata::{update_metadata_accounts_v2, Metadata, UpdateMetadataAccountsV2},
+    token::{burn, Burn, Token},
+    token_2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use raydium_cp_swap::{
     cpi::{accounts::Initialize, initialize},
@@ -14,13 +18,16 @@ use raydium_cp_swap::{
     states::{OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED},
     AUTH_SEED,
 };
+use spl_pod::optional_keys::OptionalNonZeroPubkey;
 use spl_token::solana_program::program_pack::Pack;
+use spl_token_metadata_interface::instruction::update_authority;
 
 use crate::{
     events::MigrateEvent,
     error::MigrateFundError,
     states::{bounding_curve::BoundingCurve, config::Config},
     migration_fee_receiver,
+    utils::{get_transfer_fee, revoke_mint_authorities, sort_mints, validate_mint_extensions},
     CONFIG_SEED, CURVE_RESERVE_SEED, CURVE_SEED,
 };
 
@@ -28,14 +35,19 @@ use crate::{
 pub struct MigrateFund<'info> {
     #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
     config: Box<Account<'info, Config>>,
-    #[account(address = bounding_curve.mint)]
-    mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(address = bounding_curve.pair)]
-    pair: Box<Account<'info, Mint>>,
+    pair: Box<InterfaceAccount<'info, Mint>>,
     #[account(mut, seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()], bump)]
     bounding_curve: Box<Account<'info, BoundingCurve>>,
-    #[account(mut, associated_token::mint=mint, associated_token::authority=bounding_curve)]
-    bounding_curve_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bounding_curve,
+        associated_token::token_program = mint_token_program
+    )]
+    bounding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         seeds = [bounding_curve.key().as_ref(), CURVE_RESERVE_SEED.as_bytes()],
@@ -46,15 +58,16 @@ pub struct MigrateFund<'info> {
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = bounding_curve_reserve
+        associated_token::authority = bounding_curve_reserve,
+        associated_token::token_program = mint_token_program
     )]
-    bounding_curve_reserve_ata: Box<Account<'info, TokenAccount>>,
+    bounding_curve_reserve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = pair,
         associated_token::authority = bounding_curve_reserve
     )]
-    bounding_curve_reserve_pair_ata: Box<Account<'info, TokenAccount>>,
+    bounding_curve_reserve_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         seeds=[
@@ -81,8 +94,8 @@ pub struct MigrateFund<'info> {
           &[
             POOL_SEED.as_bytes(),
             amm_config.key().as_ref(),
-            pair.key().as_ref(),
-            mint.key().as_ref(),
+            sort_mints(pair.key(), mint.key()).0.as_ref(),
+            sort_mints(pair.key(), mint.key()).1.as_ref(),
         ],
         &amm_program.key()
       ).0
@@ -146,9 +159,19 @@ pub struct MigrateFund<'info> {
         associated_token::authority = payer,
         payer = payer
     )]
-    payer_pair_ata: Box<Account<'info, TokenAccount>>,
+    payer_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"metadata", anchor_spl::metadata::ID.as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = anchor_spl::metadata::ID
+    )]
+    /// CHECK: metaplex metadata account, only used by legacy token mints
+    metadata: Option<UncheckedAccount<'info>>,
+    token_metadata_program: Option<Program<'info, Metadata>>,
     amm_program: Program<'info, RaydiumCpSwap>,
     rent: Sysvar<'info, Rent>,
+    mint_token_program: Interface<'info, TokenInterface>,
     token_program: Program<'info, Token>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
@@ -186,8 +209,11 @@ impl<'info> MigrateFund<'info> {
                     amm_observable_state,
                     payer,
                     payer_pair_ata,
+                    metadata,
+                    token_metadata_program,
                     rent,
                     amm_program,
+                    mint_token_program,
                     token_program,
                     associated_token_program,
                     system_program,
@@ -206,6 +232,8 @@ impl<'info> MigrateFund<'info> {
             return err!(MigrateFundError::AlreadyMigrated);
         }
 
+        validate_mint_extensions(&mint.to_account_info())?;
+
         let bounding_curve_key = bounding_curve.key();
         let signer_seeds = &[
             bounding_curve_key.as_ref(),
@@ -253,7 +281,7 @@ impl<'info> MigrateFund<'info> {
 
         transfer_checked(
             CpiContext::new_with_signer(
-                token_program.to_account_info(),
+                mint_token_program.to_account_info(),
                 TransferChecked {
                     mint: mint.to_account_info(),
                     to: bounding_curve_reserve_ata.to_account_info(),
@@ -261,34 +289,65 @@ impl<'info> MigrateFund<'info> {
                     authority: bounding_curve.to_account_info(),
                 },
                 &[&[
-                    &mint_key.as_ref(),
+                    mint_key.as_ref(),
                     CURVE_SEED.as_bytes(),
                     &[bumps.bounding_curve],
                 ]],
             ),
             init_amount,
-            pair.decimals,
+            mint.decimals,
         )?;
 
+        // Transfer fee mints seed the pool with what the reserve actually received
+        let init_amount = init_amount - get_transfer_fee(&mint.to_account_info(), init_amount)?;
+
+        let pair_is_token_0 = pair.key() < mint.key();
+        let (token_0_mint, token_1_mint) = match pair_is_token_0 {
+            true => (pair.to_account_info(), mint.to_account_info()),
+            false => (mint.to_account_info(), pair.to_account_info()),
+        };
+        let (creator_token_0, creator_token_1) = match pair_is_token_0 {
+            true => (
+                bounding_curve_reserve_pair_ata.to_account_info(),
+                bounding_curve_reserve_ata.to_account_info(),
+            ),
+            false => (
+                bounding_curve_reserve_ata.to_account_info(),
+                bounding_curve_reserve_pair_ata.to_account_info(),
+            ),
+        };
+        let (token_0_vault, token_1_vault) = match pair_is_token_0 {
+            true => (amm_pair_vault.to_account_info(), amm_mint_vault.to_account_info()),
+            false => (amm_mint_vault.to_account_info(), amm_pair_vault.to_account_info()),
+        };
+        let (token_0_program, token_1_program) = match pair_is_token_0 {
+            true => (token_program.to_account_info(), mint_token_program.to_account_info()),
+            false => (mint_token_program.to_account_info(), token_program.to_account_info()),
+        };
+        let (init_amount_0, init_amount_1) = match pair_is_token_0 {
+            true => (pair_init_amount, init_amount),
+            false => (init_amount, pair_init_amount),
+        };
+
         initialize(
             CpiContext::new_with_signer(
                 amm_program.to_account_info(),
                 Initialize {
-                    token_0_mint: pair.to_account_info(),
-                    token_1_mint: mint.to_account_info(),
+                    token_0_mint,
+                    token_1_mint,
                     creator: bounding_curve_reserve.to_account_info(),
                     amm_config: amm_config.to_account_info(),
                     authority: amm_authority.to_account_info(),
                     pool_state: amm_pool_state.to_account_info(),
                     lp_mint: amm_lp_mint.to_account_info(),
-                    creator_token_0: bounding_curve_reserve_pair_ata.to_account_info(),
-                    creator_token_1: bounding_curve_reserve_ata.to_account_info(),
+                    creator_token_0,
+                    creator_token_1,
                     creator_lp_token: bounding_curve_reserve_lp_ata.to_account_info(),
-                    token_0_vault: amm_pair_vault.to_account_info(),
-                    token_1_vault: amm_mint_vault.to_account_info(),
+                    token_0_vault,
+                    token_1_vault,
                     token_program: token_program.to_account_info(),
-                    token_0_program: token_program.to_account_info(),
-                    token_1_program: token_program.to_account_info(),
+                    token_0_program,
+                    token_1_program,
                     associated_token_program: associated_token_program.to_account_info(),
                     system_program: system_program.to_account_info(),
                     rent: rent.to_account_info(),
@@ -297,12 +356,9 @@ impl<'info> MigrateFund<'info> {
                 },
                 signer_seeds,
             ),
-            pair_init_amount,
-            init_amount,
-            match params.open_time {
-              Some(open_time) => open_time,
-              None => 0
-            },
+            init_amount_0,
+            init_amount_1,
+            params.open_time.unwrap_or_default(),
         )?;
 
         let bounding_curve_reserve_lp_ata = bounding_curve_reserve_lp_ata.to_account_info();
@@ -324,12 +380,70 @@ impl<'info> MigrateFund<'info> {
         )?;
         
         
+        // Metadata becomes immutable once the curve graduates
+        let curve_signer_seeds: &[&[&[u8]]] =
+            &[&[mint_key.as_ref(), CURVE_SEED.as_bytes(), &[bumps.bounding_curve]]];
+
+        match mint_token_program.key() == token_2022::ID {
+            true => invoke_signed(
+                &update_authority(
+                    &token_2022::ID,
+                    &mint_key,
+                    &bounding_curve_key,
+                    OptionalNonZeroPubkey::default(),
+                ),
+                &[
+                    mint.to_account_info(),
+                    bounding_curve.to_account_info(),
+                    mint_token_program.to_account_info(),
+                ],
+                curve_signer_seeds,
+            )?,
+            false => {
+                let (Some(metadata), Some(token_metadata_program)) =
+                    (metadata, token_metadata_program)
+                else {
+                    return err!(MigrateFundError::MissingMetadataAccount);
+                };
+
+                update_metadata_accounts_v2(
+                    CpiContext::new_with_signer(
+                        token_metadata_program.to_account_info(),
+                        UpdateMetadataAccountsV2 {
+                            metadata: metadata.to_account_info(),
+                            update_authority: bounding_curve.to_account_info(),
+                        },
+                        curve_signer_seeds,
+                    ),
+                    None,
+                    None,
+                    None,
+                    Some(false),
+                )?;
+            }
+        }
+
+        // Launches that kept their authorities lose them on graduation, the event reports the
+        // final state so launches revoked at mint also show as revoked
+        revoke_mint_authorities(
+            &mint.to_account_info(),
+            &bounding_curve.to_account_info(),
+            &mint_token_program.to_account_info(),
+            curve_signer_seeds,
+        )?;
+
+        mint.reload()?;
+        let mint_authority_revoked = mint.mint_authority.is_none();
+        let freeze_authority_revoked = mint.freeze_authority.is_none();
+
         bounding_curve.migrated = true;
 
         let clock = Clock::get()?;
 
         emit!(MigrateEvent {
             mint: mint.key(),
+            mint_authority_revoked,
+            freeze_authority_revoked,
             timestamp: clock.unix_timestamp,
         });
 
@@ -2,50 +2,75 @@ use std::ops::Mul;
 
 use anchor_lang::{
     prelude::*,
-    system_program::{transfer, Transfer},
+    solana_program::program::{invoke, invoke_signed},
+    system_program::{create_account, transfer, CreateAccount, Transfer},
 };
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{
+        create, create_idempotent, get_associated_token_address,
+        get_associated_token_address_with_program_id, AssociatedToken, Create,
+    },
     metadata::{
         create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
         Metadata,
     },
-    token::{mint_to, transfer_checked, Mint, MintTo, Token, TokenAccount, TransferChecked},
+    token::{
+        spl_token::{self, native_mint},
+        sync_native, SyncNative, Token,
+    },
+    token_2022::{
+        self,
+        spl_token_2022::{
+            extension::{metadata_pointer, transfer_fee, ExtensionType},
+            state::Mint as Token2022Mint,
+        },
+    },
+    token_interface::{
+        initialize_mint2, mint_to, transfer_checked, InitializeMint2, Mint, MintTo, TokenAccount,
+        TokenInterface, TransferChecked,
+    },
 };
+use spl_token::solana_program::program_pack::Pack;
+use spl_token_metadata_interface::instruction::initialize as initialize_token_metadata;
 
 use curve::{
     curve::{constant_curve::ConstantCurveCalculator, CurveCalculator},
-    safe_number::safe_number::Math,
+    safe_number::Math,
 };
 use pyth_sdk_solana::state::SolanaPriceAccount;
 
 use crate::{
-    error::MintTokenError,
-    events::MintEvent,
-    metadata_fee_reciever, pyth,
+    error::{MintTokenError, SwapTokenError},
+    events::{MintEvent, SwapEvent, TradingStartEvent, VestingEvent},
+    instructions::{
+        enforce_launch_protection, enforce_presale, prepare_curve_trade, quote_buy, record_buy_slot,
+        record_curve_trade, trigger_migration, CurveQuote,
+    },
+    metadata_fee_reciever, migration_fee_receiver, pyth,
     states::{
         bounding_curve::{BoundingCurve, MigrationTarget, BOUNDING_CURVE_SIZE},
         config::Config,
+        pair_oracle::PairOracle,
+        presale_claim::{PresaleClaim, PRESALE_CLAIM_SIZE},
+        trader::{Trader, TRADER_SIZE},
+        vesting::{Vesting, VESTING_SIZE},
+    },
+    utils::{
+        apply_basis_points, get_transfer_fee, price_to_number, revoke_mint_authorities, Validate,
     },
-    utils::{price_to_number, Validate},
-    CONFIG_SEED, CURVE_RESERVE_SEED, CURVE_SEED,
+    BASIS_POINTS_DIVISOR, BUYBACK_VAULT_SEED, CONFIG_SEED, CREATOR_FEE_VAULT_SEED, CURVE_RESERVE_SEED,
+    CURVE_SEED, PAIR_ORACLE_SEED, PRESALE_CLAIM_SEED, TRADER_SEED, VESTING_SEED,
 };
 
 #[derive(Accounts)]
 #[instruction(params: MintTokenParams)]
 pub struct MintToken<'info> {
-    #[account(
-        init,
-        seeds = [params.name.as_ref(), params.symbol.as_ref(), creator.key().as_ref()],
-        bump,
-        payer = creator,
-        mint::decimals = params.decimals,
-        mint::authority = bounding_curve,
-        mint::freeze_authority = bounding_curve,
-        token::token_program = token_program
-    )]
-    mint: Box<Account<'info, Mint>>,
-    pair: Box<Account<'info, Mint>>,
+    // Either a fresh keypair signed by the client, or the name, symbol and creator address,
+    // created in the handler so token 2022 mints can be initialized with their extensions
+    #[account(mut)]
+    /// CHECK: signer or name, symbol and creator address, validated when created
+    mint: UncheckedAccount<'info>,
+    pair: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         init,
@@ -56,13 +81,15 @@ pub struct MintToken<'info> {
     )]
     bounding_curve: Box<Account<'info, BoundingCurve>>,
     #[account(
-        init,
-        payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = bounding_curve,
-        associated_token::token_program = token_program
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &bounding_curve.key(),
+            &mint.key(),
+            &mint_token_program.key()
+        )
     )]
-    bounding_curve_ata: Box<Account<'info, TokenAccount>>,
+    /// CHECK: bounding curve mint token account, created once the mint exists
+    bounding_curve_ata: UncheckedAccount<'info>,
     #[account(
         init,
         seeds = [bounding_curve.key().as_ref(), CURVE_RESERVE_SEED.as_bytes()],
@@ -74,20 +101,40 @@ pub struct MintToken<'info> {
     /// CHECK: bounding curve extra layer account for token reserves
     bounding_curve_reserve: UncheckedAccount<'info>,
     #[account(
-        init,
-        payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = bounding_curve_reserve,
-        associated_token::token_program = token_program
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &bounding_curve_reserve.key(),
+            &mint.key(),
+            &mint_token_program.key()
+        )
     )]
-    bounding_curve_reserve_ata: Box<Account<'info, TokenAccount>>,
+    /// CHECK: reserve mint token account, created once the mint exists
+    bounding_curve_reserve_ata: UncheckedAccount<'info>,
     #[account(
         init,
         payer = creator,
         associated_token::mint = pair,
         associated_token::authority = bounding_curve_reserve
     )]
-    bounding_curve_reserve_pair_ata: Box<Account<'info, TokenAccount>>,
+    bounding_curve_reserve_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        seeds = [bounding_curve.key().as_ref(), CREATOR_FEE_VAULT_SEED.as_bytes()],
+        bump,
+        payer = creator,
+        token::mint = pair,
+        token::authority = bounding_curve_reserve
+    )]
+    creator_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        seeds = [bounding_curve.key().as_ref(), BUYBACK_VAULT_SEED.as_bytes()],
+        bump,
+        payer = creator,
+        token::mint = pair,
+        token::authority = bounding_curve_reserve
+    )]
+    buyback_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(seeds=[CONFIG_SEED.as_bytes()], bump)]
     config: Box<Account<'info, Config>>,
     #[account(
@@ -102,14 +149,71 @@ pub struct MintToken<'info> {
      )]
     /// CHECK: metadata account
     metadata: UncheckedAccount<'info>,
+    #[account(seeds=[pair.key().as_ref(), PAIR_ORACLE_SEED.as_bytes()], bump, has_one = pair)]
+    pair_oracle: Box<Account<'info, PairOracle>>,
     /// CHECK:
-    #[account(owner=pyth::ID @ MintTokenError::InvalidFeedAccount)]
+    #[account(
+        owner=pyth::ID @ MintTokenError::InvalidFeedAccount,
+        address=pair_oracle.feed @ MintTokenError::InvalidFeedAccount
+    )]
     pyth_pair_usd_feed: UncheckedAccount<'info>,
     #[account(mut, address=metadata_fee_reciever::id())]
     metadata_fee_reciever: UncheckedAccount<'info>,
     #[account(mut)]
     creator: Signer<'info>,
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &creator.key(),
+            &mint.key(),
+            &mint_token_program.key()
+        )
+    )]
+    /// CHECK: creator mint token account, only created for the initial buy
+    creator_ata: UncheckedAccount<'info>,
+    #[account(mut, address = get_associated_token_address(&creator.key(), &pair.key()))]
+    /// CHECK: creator pair token account, only used for initial buys on non native pairs
+    creator_pair_ata: UncheckedAccount<'info>,
+    // Initial buys count against launch protection and the presale like any other creator buy
+    #[account(
+        init,
+        seeds = [bounding_curve.key().as_ref(), creator.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump,
+        payer = creator,
+        space = TRADER_SIZE
+    )]
+    trader: Option<Box<Account<'info, Trader>>>,
+    #[account(
+        init,
+        seeds = [
+            bounding_curve.key().as_ref(),
+            creator.key().as_ref(),
+            PRESALE_CLAIM_SEED.as_bytes(),
+        ],
+        bump,
+        payer = creator,
+        space = PRESALE_CLAIM_SIZE
+    )]
+    presale_claim: Option<Box<Account<'info, PresaleClaim>>>,
+    #[account(
+        mut,
+        address = get_associated_token_address(&migration_fee_receiver::ID, &pair.key())
+    )]
+    /// CHECK: protocol fee token account, only paid by the initial buy
+    protocol_fee_pair_ata: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [bounding_curve.key().as_ref(), VESTING_SEED.as_bytes()],
+        bump,
+        payer = creator,
+        space = VESTING_SIZE
+    )]
+    vesting: Option<Box<Account<'info, Vesting>>>,
+    #[account(mut)]
+    /// CHECK: vesting escrow token account, validated and created once the mint exists
+    vesting_ata: Option<UncheckedAccount<'info>>,
     system_program: Program<'info, System>,
+    mint_token_program: Interface<'info, TokenInterface>,
     token_program: Program<'info, Token>,
     associated_token_program: Program<'info, AssociatedToken>,
     token_metadata_program: Program<'info, Metadata>,
@@ -125,6 +229,46 @@ pub struct MintTokenParams {
     decimals: u8,
     liquidity_percentage: u8,
     migration_target: MigrationTarget,
+    launch_protection: Option<LaunchProtectionParams>,
+    restrict_same_slot_sells: bool,
+    sell_tax_basis_points: u16,
+    initial_buy_amount: Option<u64>,
+    initial_buy_presale_proof: Option<Vec<[u8; 32]>>,
+    trading_starts_at: Option<i64>,
+    presale: Option<PresaleParams>,
+    vesting: Option<VestingParams>,
+    revoke_authorities: bool,
+    transfer_fee: Option<TransferFeeParams>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LaunchProtectionParams {
+    duration: u32,
+    transaction_cap_basis_points: u16,
+    wallet_cap_basis_points: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PresaleParams {
+    root: [u8; 32],
+    duration: u32,
+    wallet_cap: u64,
+    token_cap: u64,
+}
+
+// Token 2022 transfer fee, withheld fees are harvested by the creator and the rate is fixed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TransferFeeParams {
+    basis_points: u16,
+    maximum_fee: u64,
+}
+
+// Creator allocation taken out of the supply, unlocks linearly from trading start after the cliff
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct VestingParams {
+    amount: u64,
+    cliff_duration: u32,
+    duration: u32,
 }
 
 impl MintTokenParams {
@@ -135,17 +279,108 @@ impl MintTokenParams {
 
         Ok(())
     }
+
+    pub fn validate_launch_protection(&self, config: &Config) -> Result<()> {
+        if let Some(protection) = self.launch_protection {
+            let minimum_cap = config.minimum_protection_cap_basis_points;
+
+            if
+                protection.duration > config.maximum_protection_duration ||
+                protection.transaction_cap_basis_points < minimum_cap ||
+                protection.wallet_cap_basis_points < protection.transaction_cap_basis_points ||
+                (protection.wallet_cap_basis_points as u64) > BASIS_POINTS_DIVISOR
+            {
+                return err!(MintTokenError::InvalidLaunchProtection);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Type and length header followed by update authority, mint, the three length prefixed strings
+    // and an empty additional metadata vector
+    pub fn token_metadata_space(&self) -> usize {
+        4 + 32 + 32 + 4 + self.name.len() + 4 + self.symbol.len() + 4 + self.uri.len() + 4
+    }
+
+    pub fn validate_presale(&self) -> Result<()> {
+        if let Some(presale) = self.presale {
+            if
+                presale.root == [0; 32] ||
+                presale.duration == 0 ||
+                presale.wallet_cap == 0 ||
+                presale.wallet_cap > presale.token_cap
+            {
+                return err!(MintTokenError::InvalidPresale);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_vesting(&self) -> Result<()> {
+        if let Some(vesting) = self.vesting {
+            if
+                vesting.amount == 0 ||
+                vesting.amount >= self.supply ||
+                vesting.duration == 0 ||
+                vesting.cliff_duration > vesting.duration
+            {
+                return err!(MintTokenError::InvalidVesting);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn vesting_amount(&self) -> u64 {
+        self.vesting.map_or(0, |vesting| vesting.amount)
+    }
+
+    pub fn validate_transfer_fee(&self, mint_token_program: &Pubkey) -> Result<()> {
+        if let Some(transfer_fee) = self.transfer_fee {
+            if
+                *mint_token_program != token_2022::ID ||
+                (transfer_fee.basis_points as u64) > BASIS_POINTS_DIVISOR
+            {
+                return err!(MintTokenError::InvalidTransferFee);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_sell_tax(&self, config: &Config) -> Result<()> {
+        if self.sell_tax_basis_points > config.maximum_sell_tax_basis_points {
+            return err!(MintTokenError::InvalidSellTax);
+        }
+
+        Ok(())
+    }
 }
 
 impl Validate for MintTokenParams {
     fn validate(&self) -> Result<()> {
-        return self.validate_liquidity_percentage();
+        if self.initial_buy_amount == Some(0) {
+            return err!(SwapTokenError::InvalidAmount);
+        }
+
+        self.validate_liquidity_percentage()
     }
 }
 
 impl<'info> MintToken<'info> {
     pub fn process_mint_token(context: Context<MintToken>, params: &MintTokenParams) -> Result<()> {
         params.validate()?;
+        params.validate_launch_protection(&context.accounts.config)?;
+        params.validate_sell_tax(&context.accounts.config)?;
+        params.validate_presale()?;
+        params.validate_vesting()?;
+        params.validate_transfer_fee(&context.accounts.mint_token_program.key())?;
+
+        context.accounts.create_mint(params, context.bumps.bounding_curve)?;
+        context.accounts.create_curve_token_accounts()?;
+
         let Context {
             bumps,
             accounts:
@@ -160,7 +395,7 @@ impl<'info> MintToken<'info> {
                     creator,
                     metadata_fee_reciever,
                     rent,
-                    token_program,
+                    mint_token_program,
                     token_metadata_program,
                     system_program,
                     ..
@@ -178,20 +413,7 @@ impl<'info> MintToken<'info> {
         ];
         let signer_seeds = [&signer_seeds[..]];
 
-        mint_to(
-            CpiContext::new_with_signer(
-                token_program.to_account_info(),
-                MintTo {
-                    mint: mint.to_account_info(),
-                    to: bounding_curve_ata.to_account_info(),
-                    authority: bounding_curve.to_account_info(),
-                },
-                &signer_seeds,
-            ),
-            params.supply,
-        )?;
-
-        let feed = SolanaPriceAccount::account_info_to_feed(&pyth_pair_usd_feed).unwrap();
+        let feed = SolanaPriceAccount::account_info_to_feed(pyth_pair_usd_feed).unwrap();
         let pair_usd_price = price_to_number(feed.get_price_unchecked());
 
         let metadata_fee: u64 = (config.metadata_creation_fee as u64).mul(10_u64.pow(5));
@@ -209,16 +431,16 @@ impl<'info> MintToken<'info> {
 
         let maximum_curve_pair_valuation: u64 = pair_usd_price
             .inverse_div(config.maximum_curve_usd_valuation.into())
-            .mul((10_u128).pow(9))
+            .mul((10_u128).pow(pair.decimals.into()))
             .unwrap();
 
         let minimum_curve_pair_valuation: u64 = pair_usd_price
             .inverse_div(config.minimum_curve_usd_valuation.into())
-            .mul((10_u128).pow(9))
+            .mul((10_u128).pow(pair.decimals.into()))
             .unwrap();
 
         let curve = ConstantCurveCalculator::new(
-            params.supply,
+            params.supply - params.vesting_amount(),
             params.liquidity_percentage,
             maximum_curve_pair_valuation,
         );
@@ -238,51 +460,92 @@ impl<'info> MintToken<'info> {
         bounding_curve.maximum_pair_balance = maximum_pair_balance;
         bounding_curve.virtual_token_balance = bounding_curve_supply;
         bounding_curve.virtual_pair_balance = minimum_curve_pair_valuation;
+        bounding_curve.creator = creator.key();
+        bounding_curve.same_slot_sell_restricted = params.restrict_same_slot_sells;
+        bounding_curve.sell_tax_basis_points = params.sell_tax_basis_points;
 
-        transfer_checked(
+        let clock = Clock::get()?;
+
+        // Start times in the past open trading immediately, launch protection runs from the start
+        let trading_starts_at = params
+            .trading_starts_at
+            .unwrap_or(clock.unix_timestamp)
+            .max(clock.unix_timestamp);
+        bounding_curve.trading_starts_at = trading_starts_at;
+
+        if let Some(protection) = params.launch_protection {
+            bounding_curve.protection_ends_at = trading_starts_at + (protection.duration as i64);
+            bounding_curve.protection_transaction_cap =
+                apply_basis_points(bounding_curve_supply, protection.transaction_cap_basis_points);
+            bounding_curve.protection_wallet_cap =
+                apply_basis_points(bounding_curve_supply, protection.wallet_cap_basis_points);
+        }
+
+        // Presale opens with trading and restricts buys to the allowlist until it ends
+        if let Some(presale) = params.presale {
+            bounding_curve.presale_root = presale.root;
+            bounding_curve.presale_ends_at = trading_starts_at + (presale.duration as i64);
+            bounding_curve.presale_wallet_cap = presale.wallet_cap;
+            bounding_curve.presale_token_cap = presale.token_cap;
+        }
+
+        // Minted straight into each account so transfer fee mints start with the full curve supply
+        mint_to(
             CpiContext::new_with_signer(
-                token_program.to_account_info(),
-                TransferChecked {
+                mint_token_program.to_account_info(),
+                MintTo {
                     mint: mint.to_account_info(),
-                    from: bounding_curve_ata.to_account_info(),
                     to: bounding_curve_reserve_ata.to_account_info(),
                     authority: bounding_curve.to_account_info(),
                 },
                 &signer_seeds,
             ),
             bounding_curve_supply,
-            params.decimals,
         )?;
 
-        create_metadata_accounts_v3(
+        mint_to(
             CpiContext::new_with_signer(
-                token_metadata_program.to_account_info(),
-                CreateMetadataAccountsV3 {
-                    payer: creator.to_account_info(),
+                mint_token_program.to_account_info(),
+                MintTo {
                     mint: mint.to_account_info(),
-                    rent: rent.to_account_info(),
-                    system_program: system_program.to_account_info(),
-                    metadata: metadata.to_account_info(),
-                    mint_authority: bounding_curve.to_account_info(),
-                    update_authority: bounding_curve.to_account_info(),
+                    to: bounding_curve_ata.to_account_info(),
+                    authority: bounding_curve.to_account_info(),
                 },
                 &signer_seeds,
             ),
-            DataV2 {
-                name: params.name.clone(),
-                symbol: params.symbol.clone(),
-                uri: params.uri.clone(),
-                uses: None,
-                creators: None,
-                collection: None,
-                seller_fee_basis_points: 0,
-            },
-            false,
-            true,
-            None,
+            params.supply - params.vesting_amount() - bounding_curve_supply,
         )?;
 
-        let clock = Clock::get()?;
+        // Token 2022 mints already hold their metadata through the token metadata extension
+        if mint_token_program.key() != token_2022::ID {
+            create_metadata_accounts_v3(
+                CpiContext::new_with_signer(
+                    token_metadata_program.to_account_info(),
+                    CreateMetadataAccountsV3 {
+                        payer: creator.to_account_info(),
+                        mint: mint.to_account_info(),
+                        rent: rent.to_account_info(),
+                        system_program: system_program.to_account_info(),
+                        metadata: metadata.to_account_info(),
+                        mint_authority: bounding_curve.to_account_info(),
+                        update_authority: bounding_curve.to_account_info(),
+                    },
+                    &signer_seeds,
+                ),
+                DataV2 {
+                    name: params.name.clone(),
+                    symbol: params.symbol.clone(),
+                    uri: params.uri.clone(),
+                    uses: None,
+                    creators: None,
+                    collection: None,
+                    seller_fee_basis_points: 0,
+                },
+                false,
+                true,
+                None,
+            )?;
+        }
 
         emit!(MintEvent {
             mint: mint.key(),
@@ -293,8 +556,432 @@ impl<'info> MintToken<'info> {
             decimals: pair.decimals,
             bounding_curve: bounding_curve.key(),
             creator: creator.key(),
+            authorities_revoked: params.revoke_authorities,
+            timestamp: clock.unix_timestamp,
+        });
+
+        if trading_starts_at > clock.unix_timestamp {
+            emit!(TradingStartEvent {
+                trading_starts_at,
+                mint: mint.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        if let Some(vesting) = params.vesting {
+            context
+                .accounts
+                .create_vesting(&vesting, trading_starts_at, bumps.bounding_curve, bumps.vesting)?;
+        }
+
+        // Creator buy is part of the launch, so it goes through ahead of a scheduled start
+        if let Some(initial_buy_amount) = params.initial_buy_amount {
+            context.accounts.process_initial_buy(
+                initial_buy_amount,
+                &params.initial_buy_presale_proof,
+                params.decimals,
+                bumps.bounding_curve_reserve,
+            )?;
+        }
+
+        // Authorities can only go once the vesting and initial buy mints are done
+        if params.revoke_authorities {
+            let accounts = &context.accounts;
+            revoke_mint_authorities(
+                &accounts.mint.to_account_info(),
+                &accounts.bounding_curve.to_account_info(),
+                &accounts.mint_token_program.to_account_info(),
+                &signer_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Token 2022 mints are created with a metadata pointer to themselves and the token metadata
+    // extension, legacy mints get a metaplex metadata account once minted
+    fn create_mint(&self, params: &MintTokenParams, bounding_curve_bump: u8) -> Result<()> {
+        let is_token_2022 = self.mint_token_program.key() == token_2022::ID;
+        let mint_key = self.mint.key();
+        let bounding_curve_key = self.bounding_curve.key();
+
+        let extensions = match params.transfer_fee {
+            Some(_) => vec![ExtensionType::MetadataPointer, ExtensionType::TransferFeeConfig],
+            None => vec![ExtensionType::MetadataPointer],
+        };
+        let (space, metadata_space) = match is_token_2022 {
+            true => (
+                ExtensionType::try_calculate_account_len::<Token2022Mint>(&extensions)?,
+                params.token_metadata_space(),
+            ),
+            false => (spl_token::state::Mint::LEN, 0),
+        };
+
+        // Token metadata reallocates the mint on initialize, so rent covers it upfront
+        let lamports = Rent::get()?.minimum_balance(space + metadata_space);
+        let create_mint_context = CpiContext::new(
+            self.system_program.to_account_info(),
+            CreateAccount {
+                from: self.creator.to_account_info(),
+                to: self.mint.to_account_info(),
+            },
+        );
+
+        // Keypair mints sign the transaction, name, symbol and creator mints sign with their seeds
+        match self.mint.is_signer {
+            true => create_account(
+                create_mint_context,
+                lamports,
+                space as u64,
+                &self.mint_token_program.key(),
+            )?,
+            false => {
+                let creator_key = self.creator.key();
+                let (name, symbol) = (params.name.as_bytes(), params.symbol.as_bytes());
+                let (_, bump) = Pubkey::try_find_program_address(
+                    &[name, symbol, creator_key.as_ref()],
+                    &crate::ID,
+                )
+                .filter(|(address, _)| *address == mint_key)
+                .ok_or(MintTokenError::InvalidMint)?;
+
+                let signer_seeds = &[name, symbol, creator_key.as_ref(), &[bump]];
+
+                create_account(
+                    create_mint_context.with_signer(&[&signer_seeds[..]]),
+                    lamports,
+                    space as u64,
+                    &self.mint_token_program.key(),
+                )?
+            }
+        }
+
+        if is_token_2022 {
+            invoke(
+                &metadata_pointer::instruction::initialize(
+                    &token_2022::ID,
+                    &mint_key,
+                    Some(bounding_curve_key),
+                    Some(mint_key),
+                )?,
+                &[self.mint.to_account_info(), self.mint_token_program.to_account_info()],
+            )?;
+        }
+
+        if let Some(fee) = params.transfer_fee {
+            invoke(
+                &transfer_fee::instruction::initialize_transfer_fee_config(
+                    &token_2022::ID,
+                    &mint_key,
+                    None,
+                    Some(&self.creator.key()),
+                    fee.basis_points,
+                    fee.maximum_fee,
+                )?,
+                &[self.mint.to_account_info(), self.mint_token_program.to_account_info()],
+            )?;
+        }
+
+        initialize_mint2(
+            CpiContext::new(
+                self.mint_token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            params.decimals,
+            &bounding_curve_key,
+            Some(&bounding_curve_key),
+        )?;
+
+        if is_token_2022 {
+            invoke_signed(
+                &initialize_token_metadata(
+                    &token_2022::ID,
+                    &mint_key,
+                    &bounding_curve_key,
+                    &mint_key,
+                    &bounding_curve_key,
+                    params.name.clone(),
+                    params.symbol.clone(),
+                    params.uri.clone(),
+                ),
+                &[
+                    self.mint.to_account_info(),
+                    self.bounding_curve.to_account_info(),
+                    self.mint_token_program.to_account_info(),
+                ],
+                &[&[
+                    mint_key.as_ref(),
+                    CURVE_SEED.as_bytes(),
+                    &[bounding_curve_bump],
+                ]],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn create_curve_token_accounts(&self) -> Result<()> {
+        for (associated_token, authority) in [
+            (&self.bounding_curve_ata, self.bounding_curve.to_account_info()),
+            (&self.bounding_curve_reserve_ata, self.bounding_curve_reserve.to_account_info()),
+        ] {
+            create(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                Create {
+                    authority,
+                    payer: self.creator.to_account_info(),
+                    associated_token: associated_token.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.mint_token_program.to_account_info(),
+                },
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    // Creator allocation is minted straight into an escrow owned by the vesting account
+    fn create_vesting(
+        &mut self,
+        params: &VestingParams,
+        start_at: i64,
+        bounding_curve_bump: u8,
+        vesting_bump: u8,
+    ) -> Result<()> {
+        let (Some(vesting), Some(vesting_ata)) = (&mut self.vesting, &self.vesting_ata) else {
+            return err!(MintTokenError::InvalidVesting);
+        };
+
+        let mint_key = self.mint.key();
+        if vesting_ata.key()
+            != get_associated_token_address_with_program_id(
+                &vesting.key(),
+                &mint_key,
+                &self.mint_token_program.key(),
+            )
+        {
+            return err!(MintTokenError::InvalidVesting);
+        }
+
+        create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.creator.to_account_info(),
+                associated_token: vesting_ata.to_account_info(),
+                authority: vesting.to_account_info(),
+                mint: self.mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.mint_token_program.to_account_info(),
+            },
+        ))?;
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.mint_token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: vesting_ata.to_account_info(),
+                    authority: self.bounding_curve.to_account_info(),
+                },
+                &[&[
+                    mint_key.as_ref(),
+                    CURVE_SEED.as_bytes(),
+                    &[bounding_curve_bump],
+                ]],
+            ),
+            params.amount,
+        )?;
+
+        vesting.bounding_curve = self.bounding_curve.key();
+        vesting.beneficiary = self.creator.key();
+        vesting.total_amount = params.amount;
+        vesting.start_at = start_at;
+        vesting.cliff_at = start_at + (params.cliff_duration as i64);
+        vesting.end_at = start_at + (params.duration as i64);
+        vesting.bump = vesting_bump;
+
+        let clock = Clock::get()?;
+
+        emit!(VestingEvent {
+            mint: mint_key,
+            vesting: vesting.key(),
+            beneficiary: vesting.beneficiary,
+            total_amount: vesting.total_amount,
+            start_at: vesting.start_at,
+            cliff_at: vesting.cliff_at,
+            end_at: vesting.end_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Creator buy runs in the launch instruction so it can't be front-run, priced like any swap buy
+    #[inline(never)]
+    fn process_initial_buy(
+        &mut self,
+        amount: u64,
+        presale_proof: &Option<Vec<[u8; 32]>>,
+        decimals: u8,
+        curve_reserve_bump: u8,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        prepare_curve_trade(&self.config, &mut self.bounding_curve, &clock)?;
+        let pair_balance = self.bounding_curve.virtual_pair_balance;
+
+        let CurveQuote {
+            amount_in,
+            amount_out,
+            fees,
+            ..
+        } = quote_buy(&self.config, &self.bounding_curve, amount, false);
+
+        let bounding_curve_key = self.bounding_curve.key();
+        let creator_key = self.creator.key();
+
+        if let Some(trader) = &mut self.trader {
+            trader.bounding_curve = bounding_curve_key;
+            trader.wallet = creator_key;
+        }
+        if let Some(presale_claim) = &mut self.presale_claim {
+            presale_claim.bounding_curve = bounding_curve_key;
+            presale_claim.wallet = creator_key;
+        }
+
+        let trader = self.trader.as_deref_mut().map(|trader| &mut **trader);
+        enforce_launch_protection(&self.bounding_curve, trader, amount_out, clock.unix_timestamp)?;
+        enforce_presale(
+            &mut self.bounding_curve,
+            self.presale_claim.as_deref_mut().map(|presale_claim| &mut **presale_claim),
+            creator_key,
+            amount_out,
+            presale_proof,
+            clock.unix_timestamp,
+        )?;
+        let trader = self.trader.as_deref_mut().map(|trader| &mut **trader);
+        record_buy_slot(&self.bounding_curve, trader, clock.slot)?;
+
+        self.transfer_from_creator(
+            self.bounding_curve_reserve_pair_ata.to_account_info(),
+            amount_in,
+        )?;
+        self.transfer_from_creator(self.creator_fee_vault.to_account_info(), fees.creator)?;
+        self.transfer_from_creator(
+            self.protocol_fee_pair_ata.to_account_info(),
+            fees.protocol(),
+        )?;
+
+        create_idempotent(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.creator.to_account_info(),
+                associated_token: self.creator_ata.to_account_info(),
+                authority: self.creator.to_account_info(),
+                mint: self.mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.mint_token_program.to_account_info(),
+            },
+        ))?;
+
+        let signer_seeds = &[
+            bounding_curve_key.as_ref(),
+            CURVE_RESERVE_SEED.as_bytes(),
+            &[curve_reserve_bump],
+        ];
+        let signer_seeds = &[&signer_seeds[..]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.mint_token_program.to_account_info(),
+                TransferChecked {
+                    mint: self.mint.to_account_info(),
+                    from: self.bounding_curve_reserve_ata.to_account_info(),
+                    to: self.creator_ata.to_account_info(),
+                    authority: self.bounding_curve_reserve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_out,
+            decimals,
+        )?;
+
+        let bounding_curve = &mut self.bounding_curve;
+        bounding_curve.add(self.pair.key(), amount_in);
+        bounding_curve.sub(self.mint.key(), amount_out);
+        bounding_curve.traded = true;
+
+        // Reserve pays out the full amount, transfer fee mints withhold part of it from the creator
+        let amount_received = amount_out - get_transfer_fee(&self.mint.to_account_info(), amount_out)?;
+
+        // A creator buy that fills the curve stops trading like any other buy
+        self.bounding_curve_reserve_pair_ata.reload()?;
+        trigger_migration(
+            bounding_curve,
+            self.bounding_curve_reserve_pair_ata.amount,
+            clock.unix_timestamp,
+        );
+        record_curve_trade(
+            &self.config,
+            bounding_curve,
+            pair_balance,
+            amount_in + fees.total,
+            &clock,
+        );
+
+        emit!(SwapEvent {
+            mint: self.mint.key(),
+            token_amount: amount_received,
+            pair_amount: amount_in + fees.total,
+            virtual_token_balance: bounding_curve.virtual_token_balance,
+            virtual_pair_balance: bounding_curve.virtual_pair_balance,
+            market_cap: self.bounding_curve_reserve_pair_ata.amount,
+            trade_direction: 0,
+            payer: self.creator.key(),
             timestamp: clock.unix_timestamp,
         });
+
         Ok(())
     }
+
+    // Native pairs are paid in lamports, any other pair from the creator pair token account
+    fn transfer_from_creator(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if self.pair.key() == native_mint::ID {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.creator.to_account_info(),
+                        to: to.clone(),
+                    },
+                ),
+                amount,
+            )?;
+
+            return sync_native(CpiContext::new(
+                self.token_program.to_account_info(),
+                SyncNative { account: to },
+            ));
+        }
+
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    to,
+                    mint: self.pair.to_account_info(),
+                    from: self.creator_pair_ata.to_account_info(),
+                    authority: self.creator.to_account_info(),
+                },
+            ),
+            amount,
+            self.pair.decimals,
+        )
+    }
 }
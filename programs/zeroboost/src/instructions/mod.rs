@@ -2,8 +2,32 @@ pub mod swap;
 pub mod initialize_config;
 pub mod mint_token;
 pub mod migrate_fund;
+pub mod claim_creator_fees;
+pub mod register_referrer;
+pub mod set_pair_oracle;
+pub mod place_limit_order;
+pub mod cancel_limit_order;
+pub mod fill_limit_order;
+pub mod swap_route;
+pub mod buyback_and_burn;
+pub mod quote_swap;
+pub mod claim_vested;
+pub mod cancel_launch;
+pub mod update_metadata;
 
 pub use swap::*;
 pub use initialize_config::*;
 pub use mint_token::*;
 pub use migrate_fund::*;
+pub use claim_creator_fees::*;
+pub use register_referrer::*;
+pub use set_pair_oracle::*;
+pub use place_limit_order::*;
+pub use cancel_limit_order::*;
+pub use fill_limit_order::*;
+pub use swap_route::*;
+pub use buyback_and_burn::*;
+pub use quote_swap::*;
+pub use claim_vested::*;
+pub use cancel_launch::*;
+pub use update_metadata::*;
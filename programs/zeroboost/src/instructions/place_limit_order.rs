@@ -0,0 +1,228 @@
+use anchor_lang::{ prelude::*, system_program::{ transfer, Transfer } };
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ spl_token::native_mint, sync_native, SyncNative, Token },
+    token_interface::{
+        transfer_checked,
+        Mint,
+        TokenAccount,
+        TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    error::SwapTokenError,
+    events::LimitOrderPlacedEvent,
+    states::{
+        bounding_curve::BoundingCurve,
+        config::Config,
+        limit_order::{ LimitOrder, LIMIT_ORDER_SIZE },
+        trader::{ Trader, TRADER_SIZE },
+    },
+    utils::Validate,
+    CONFIG_SEED,
+    CURVE_SEED,
+    LIMIT_ORDER_SEED,
+    TRADER_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(params: PlaceLimitOrderParams)]
+pub struct PlaceLimitOrder<'info> {
+    #[account(address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = bounding_curve.pair)]
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    config: Box<Account<'info, Config>>,
+    #[account(seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()], bump)]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    #[account(
+        init,
+        seeds = [
+            bounding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            params.id.to_le_bytes().as_ref(),
+            LIMIT_ORDER_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = LIMIT_ORDER_SIZE
+    )]
+    limit_order: Box<Account<'info, LimitOrder>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = limit_order,
+        associated_token::token_program = mint_token_program
+    )]
+    limit_order_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = pair,
+        associated_token::authority = limit_order
+    )]
+    limit_order_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        seeds = [bounding_curve.key().as_ref(), limit_order.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump,
+        payer = owner,
+        space = TRADER_SIZE
+    )]
+    limit_order_trader: Box<Account<'info, Trader>>,
+    // Owner record fills are checked against, created here so keepers never pay its rent
+    #[account(
+        init_if_needed,
+        seeds = [bounding_curve.key().as_ref(), owner.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump,
+        payer = owner,
+        space = TRADER_SIZE
+    )]
+    owner_trader: Box<Account<'info, Trader>>,
+    #[account(mut)]
+    owner: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = mint_token_program
+    )]
+    owner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = pair,
+        associated_token::authority = owner
+    )]
+    owner_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    system_program: Program<'info, System>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct PlaceLimitOrderParams {
+    id: u64,
+    trade_direction: u8,
+    amount_in: u64,
+    minimum_amount_out: u64,
+}
+
+impl Validate for PlaceLimitOrderParams {
+    fn validate(&self) -> Result<()> {
+        if self.amount_in == 0 || self.minimum_amount_out == 0 {
+            return err!(SwapTokenError::InvalidAmount);
+        }
+        if self.trade_direction > 1 {
+            return err!(SwapTokenError::InvalidTradeDirection);
+        }
+        Ok(())
+    }
+}
+
+impl<'info> PlaceLimitOrder<'info> {
+    pub fn process_place_limit_order(
+        context: Context<PlaceLimitOrder>,
+        params: &PlaceLimitOrderParams
+    ) -> Result<()> {
+        params.validate()?;
+
+        if !context.accounts.bounding_curve.tradeable {
+            return err!(SwapTokenError::NotTradeable);
+        }
+
+        let accounts = &mut *context.accounts;
+        let keeper_tip = accounts.config.limit_order_keeper_tip;
+
+        // Orders escrow the input side: pair for buys, mint for sells
+        match params.trade_direction {
+            0 if accounts.pair.key() == native_mint::ID => {
+                transfer(
+                    CpiContext::new(accounts.system_program.to_account_info(), Transfer {
+                        from: accounts.owner.to_account_info(),
+                        to: accounts.limit_order_pair_ata.to_account_info(),
+                    }),
+                    params.amount_in
+                )?;
+
+                sync_native(
+                    CpiContext::new(accounts.token_program.to_account_info(), SyncNative {
+                        account: accounts.limit_order_pair_ata.to_account_info(),
+                    })
+                )?;
+            }
+            0 =>
+                transfer_checked(
+                    CpiContext::new(accounts.token_program.to_account_info(), TransferChecked {
+                        mint: accounts.pair.to_account_info(),
+                        from: accounts.owner_pair_ata.to_account_info(),
+                        to: accounts.limit_order_pair_ata.to_account_info(),
+                        authority: accounts.owner.to_account_info(),
+                    }),
+                    params.amount_in,
+                    accounts.pair.decimals
+                )?,
+            _ =>
+                transfer_checked(
+                    CpiContext::new(accounts.mint_token_program.to_account_info(), TransferChecked {
+                        mint: accounts.mint.to_account_info(),
+                        from: accounts.owner_ata.to_account_info(),
+                        to: accounts.limit_order_ata.to_account_info(),
+                        authority: accounts.owner.to_account_info(),
+                    }),
+                    params.amount_in,
+                    accounts.mint.decimals
+                )?,
+        }
+
+        transfer(
+            CpiContext::new(accounts.system_program.to_account_info(), Transfer {
+                from: accounts.owner.to_account_info(),
+                to: accounts.limit_order.to_account_info(),
+            }),
+            keeper_tip
+        )?;
+
+        let limit_order_key = accounts.limit_order.key();
+        let bounding_curve_key = accounts.bounding_curve.key();
+
+        let limit_order = &mut accounts.limit_order;
+        limit_order.owner = accounts.owner.key();
+        limit_order.bounding_curve = bounding_curve_key;
+        limit_order.id = params.id;
+        limit_order.trade_direction = params.trade_direction;
+        limit_order.amount_in = params.amount_in;
+        limit_order.minimum_amount_out = params.minimum_amount_out;
+        limit_order.keeper_tip = keeper_tip;
+        limit_order.bump = context.bumps.limit_order;
+
+        // Trader record lets the order trade on curves that require one without paying rent at fill
+        let limit_order_trader = &mut accounts.limit_order_trader;
+        limit_order_trader.bounding_curve = bounding_curve_key;
+        limit_order_trader.wallet = limit_order_key;
+
+        let owner_trader = &mut accounts.owner_trader;
+        owner_trader.bounding_curve = bounding_curve_key;
+        owner_trader.wallet = accounts.owner.key();
+
+        let clock = Clock::get()?;
+
+        emit!(LimitOrderPlacedEvent {
+            mint: accounts.mint.key(),
+            limit_order: limit_order_key,
+            owner: accounts.owner.key(),
+            trade_direction: params.trade_direction,
+            amount_in: params.amount_in,
+            minimum_amount_out: params.minimum_amount_out,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
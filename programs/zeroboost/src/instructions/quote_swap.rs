@@ -0,0 +1,416 @@
+use std::mem::size_of;
+
+use anchor_lang::{ prelude::*, Discriminator };
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        get_associated_token_address_with_program_id,
+        AssociatedToken,
+    },
+    token::{ spl_token::native_mint, Token },
+    token_interface::{ Mint, TokenAccount, TokenInterface },
+};
+use raydium_cp_swap::{
+    curve::CurveCalculator,
+    program::RaydiumCpSwap,
+    states::{ AmmConfig, PoolState },
+};
+
+use crate::{
+    error::SwapTokenError,
+    instructions::{
+        enforce_launch_protection,
+        enforce_presale,
+        enforce_same_slot_sell_restriction,
+        enforce_trading_start,
+        is_migration_reached,
+        prepare_curve_trade,
+        quote_buy,
+        quote_sell,
+        record_buy_slot,
+        validate_referral,
+        CurveQuote,
+        SwapParams,
+        SwapResult,
+        SwapFees,
+    },
+    migration_fee_receiver,
+    states::{
+        bounding_curve::BoundingCurve,
+        config::Config,
+        presale_claim::PresaleClaim,
+        referral::Referral,
+        trader::Trader,
+    },
+    utils::{
+        apply_basis_points,
+        get_amm_pool_state_address,
+        get_transfer_amount_with_fee,
+        get_transfer_fee,
+        token_account_amount,
+        validate_mint_extensions,
+        Validate,
+    },
+    BUYBACK_VAULT_SEED,
+    CONFIG_SEED,
+    CREATOR_FEE_VAULT_SEED,
+    CURVE_RESERVE_SEED,
+    CURVE_SEED,
+    PRESALE_CLAIM_SEED,
+    TRADER_SEED,
+};
+
+// Same accounts in the same order as swap but read only, clients quote with their trade keys
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    #[account(address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = bounding_curve.pair)]
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    config: Box<Account<'info, Config>>,
+    #[account(seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()], bump)]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    #[account(seeds = [bounding_curve.key().as_ref(), CURVE_RESERVE_SEED.as_bytes()], bump)]
+    /// CHECK: bounding curve extra layer account for token reserve
+    bounding_curve_reserve: UncheckedAccount<'info>,
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = bounding_curve_reserve,
+        associated_token::token_program = mint_token_program
+    )]
+    bounding_curve_reserve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        associated_token::mint = pair,
+        associated_token::authority = bounding_curve_reserve
+    )]
+    bounding_curve_reserve_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(seeds = [bounding_curve.key().as_ref(), CREATOR_FEE_VAULT_SEED.as_bytes()], bump)]
+    creator_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = get_associated_token_address(&migration_fee_receiver::ID, &pair.key()))]
+    protocol_fee_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(seeds = [bounding_curve.key().as_ref(), BUYBACK_VAULT_SEED.as_bytes()], bump)]
+    buyback_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    referral: Option<Box<Account<'info, Referral>>>,
+    referrer_pair_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        seeds = [bounding_curve.key().as_ref(), payer.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump
+    )]
+    /// CHECK: created by the first swap, quoted from empty state until then
+    trader: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [
+            bounding_curve.key().as_ref(),
+            payer.key().as_ref(),
+            PRESALE_CLAIM_SEED.as_bytes(),
+        ],
+        bump
+    )]
+    /// CHECK: created by the first presale buy, quoted from empty state until then
+    presale_claim: Option<UncheckedAccount<'info>>,
+    /// CHECK: wallet the quote is for, quotes don't need its signature
+    payer: UncheckedAccount<'info>,
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &payer.key(),
+            &mint.key(),
+            &mint_token_program.key()
+        )
+    )]
+    /// CHECK: payer mint token account, may not exist yet
+    payer_ata: UncheckedAccount<'info>,
+    #[account(address = get_associated_token_address(&payer.key(), &pair.key()))]
+    /// CHECK: payer pair token account, may not exist yet
+    payer_pair_ata: UncheckedAccount<'info>,
+    /// CHECK: temporary wrapped pair account for native swaps, unused by quotes
+    payer_native_pair: Option<UncheckedAccount<'info>>,
+    /// CHECK: raydium cp pool accounts, only required once the curve migrated
+    amm_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by raydium
+    amm_authority: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated against the pool created on migration
+    amm_pool_state: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated against the pool vaults
+    amm_mint_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated against the pool vaults
+    amm_pair_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by raydium
+    amm_observable_state: Option<UncheckedAccount<'info>>,
+    amm_program: Option<Program<'info, RaydiumCpSwap>>,
+    system_program: Program<'info, System>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Trader and presale claim accounts start zeroed in swap, so a missing one quotes as empty
+fn load_or_default<T: AccountDeserialize + Default>(
+    account: &Option<UncheckedAccount>
+) -> Result<Option<T>> {
+    match account {
+        Some(account) if account.data_is_empty() => Ok(Some(T::default())),
+        Some(account) => Ok(Some(T::try_deserialize(&mut &account.try_borrow_data()?[..])?)),
+        None => Ok(None),
+    }
+}
+
+impl<'info> QuoteSwap<'info> {
+    pub fn process_quote_swap(context: Context<QuoteSwap>, params: &SwapParams) -> Result<SwapResult> {
+        params.validate()?;
+
+        let accounts = &context.accounts;
+        let config = &accounts.config;
+        let mint_info = accounts.mint.to_account_info();
+
+        validate_referral(
+            accounts.referral.as_deref(),
+            accounts.referrer_pair_ata.as_deref(),
+            accounts.pair.key(),
+            accounts.payer.key()
+        )?;
+        validate_mint_extensions(&mint_info)?;
+
+        if params.native.is_some() && accounts.pair.key() != native_mint::ID {
+            return err!(SwapTokenError::InvalidNativePair);
+        }
+
+        if accounts.bounding_curve.migrated {
+            let mut result = accounts.quote_amm_swap(params)?;
+            result.virtual_token_balance = accounts.bounding_curve.virtual_token_balance;
+            result.virtual_pair_balance = accounts.bounding_curve.virtual_pair_balance;
+
+            return Ok(result);
+        }
+
+        if !accounts.bounding_curve.tradeable {
+            return err!(SwapTokenError::NotTradeable);
+        }
+
+        let clock = Clock::get()?;
+
+        // Swap checks run against copies so the quote fails where swap would without writing state
+        let mut bounding_curve = accounts.bounding_curve.copy();
+        let mut trader = load_or_default::<Trader>(&accounts.trader)?;
+        let mut presale_claim = load_or_default::<PresaleClaim>(&accounts.presale_claim)?;
+
+        enforce_trading_start(&bounding_curve, clock.unix_timestamp)?;
+        prepare_curve_trade(config, &mut bounding_curve, &clock)?;
+
+        let referred = accounts.referral.is_some();
+        let pair = accounts.pair.key();
+        let mint = accounts.mint.key();
+
+        let mut result = match params.trade_direction {
+            0 => {
+                let CurveQuote { amount_in, amount_out, fees, .. } = quote_buy(
+                    config,
+                    &bounding_curve,
+                    params.amount,
+                    referred
+                );
+
+                enforce_launch_protection(
+                    &bounding_curve,
+                    trader.as_mut(),
+                    amount_out,
+                    clock.unix_timestamp
+                )?;
+                enforce_presale(
+                    &mut bounding_curve,
+                    presale_claim.as_mut(),
+                    accounts.payer.key(),
+                    amount_out,
+                    &params.presale_proof,
+                    clock.unix_timestamp
+                )?;
+                record_buy_slot(&bounding_curve, trader.as_mut(), clock.slot)?;
+
+                bounding_curve.add(pair, amount_in);
+                bounding_curve.sub(mint, amount_out);
+
+                let amount_received = amount_out - get_transfer_fee(&mint_info, amount_out)?;
+                let reserve_pair_balance =
+                    accounts.bounding_curve_reserve_pair_ata.amount + amount_in;
+
+                let mut result = SwapResult::new(amount_received, amount_in + fees.total, &fees);
+                result.migration_triggered =
+                    is_migration_reached(&bounding_curve, reserve_pair_balance);
+                result
+            }
+            1 => {
+                enforce_same_slot_sell_restriction(&bounding_curve, trader.as_ref(), clock.slot)?;
+
+                let transfer_fee = get_transfer_fee(&mint_info, params.amount)?;
+                let CurveQuote { amount_in, amount_out, fees, sell_tax } = quote_sell(
+                    config,
+                    &bounding_curve,
+                    params.amount - transfer_fee,
+                    referred
+                );
+                let amount_sent = get_transfer_amount_with_fee(&mint_info, amount_in)?;
+
+                bounding_curve.sub(pair, amount_out);
+                bounding_curve.add(mint, amount_in);
+
+                let mut result = SwapResult::new(
+                    amount_sent,
+                    amount_out - fees.total - sell_tax,
+                    &fees
+                );
+                result.sell_tax = sell_tax;
+                result
+            }
+            _ => {
+                return err!(SwapTokenError::InvalidTradeDirection);
+            }
+        };
+
+        result.virtual_token_balance = bounding_curve.virtual_token_balance;
+        result.virtual_pair_balance = bounding_curve.virtual_pair_balance;
+
+        Ok(result)
+    }
+
+    // Prices the trade with the raydium cp curve against the current pool vault balances
+    fn quote_amm_swap(&self, params: &SwapParams) -> Result<SwapResult> {
+        let (
+            Some(amm_config),
+            Some(amm_pool_state),
+            Some(amm_mint_vault),
+            Some(amm_pair_vault),
+            Some(amm_program),
+        ) = (
+            &self.amm_config,
+            &self.amm_pool_state,
+            &self.amm_mint_vault,
+            &self.amm_pair_vault,
+            &self.amm_program,
+        ) else {
+            return err!(SwapTokenError::MissingAmmAccounts);
+        };
+
+        let amm_pool_state_address = get_amm_pool_state_address(
+            &amm_config.key(),
+            self.mint.key(),
+            self.pair.key(),
+            &amm_program.key()
+        );
+
+        if amm_pool_state.key() != amm_pool_state_address {
+            return err!(SwapTokenError::InvalidAmmPool);
+        }
+
+        let amm = params.amm.ok_or(SwapTokenError::MissingAmmSwapParams)?;
+        let amm_config = AmmConfig::try_deserialize(&mut &amm_config.try_borrow_data()?[..])?;
+        let pool_state_data = amm_pool_state.try_borrow_data()?;
+
+        if
+            pool_state_data.len() < 8 + size_of::<PoolState>() ||
+            pool_state_data[..8] != PoolState::DISCRIMINATOR
+        {
+            return err!(SwapTokenError::InvalidAmmPool);
+        }
+
+        let pool_state = bytemuck::from_bytes::<PoolState>(
+            &pool_state_data[8..8 + size_of::<PoolState>()]
+        );
+
+        let mint_is_token_0 = pool_state.token_0_mint == self.mint.key();
+        let (mint_vault, pair_vault) = match mint_is_token_0 {
+            true => (pool_state.token_0_vault, pool_state.token_1_vault),
+            false => (pool_state.token_1_vault, pool_state.token_0_vault),
+        };
+
+        if amm_mint_vault.key() != mint_vault || amm_pair_vault.key() != pair_vault {
+            return err!(SwapTokenError::InvalidAmmPool);
+        }
+
+        let clock = Clock::get()?;
+
+        if (clock.unix_timestamp as u64) < pool_state.open_time {
+            return err!(SwapTokenError::TradingNotStarted);
+        }
+
+        let mint_vault_amount = token_account_amount(amm_mint_vault)?;
+        let pair_vault_amount = token_account_amount(amm_pair_vault)?;
+        let (mint_reserve, pair_reserve) = match mint_is_token_0 {
+            true => pool_state.vault_amount_without_fee(mint_vault_amount, pair_vault_amount),
+            false => {
+                let (pair_reserve, mint_reserve) = pool_state.vault_amount_without_fee(
+                    pair_vault_amount,
+                    mint_vault_amount
+                );
+                (mint_reserve, pair_reserve)
+            }
+        };
+
+        let mint_info = self.mint.to_account_info();
+        let pair_info = self.pair.to_account_info();
+        let (input_mint, output_mint, input_reserve, output_reserve) = match
+            params.trade_direction
+        {
+            0 => (&pair_info, &mint_info, pair_reserve, mint_reserve),
+            1 => (&mint_info, &pair_info, mint_reserve, pair_reserve),
+            _ => {
+                return err!(SwapTokenError::InvalidTradeDirection);
+            }
+        };
+
+        // Transfer fees are taken on the way into and out of the pool like raydium does
+        let (amount_in, amount_out) = match amm.base_output {
+            true => {
+                let swap = CurveCalculator::swap_base_output(
+                    get_transfer_amount_with_fee(output_mint, params.amount)? as u128,
+                    input_reserve as u128,
+                    output_reserve as u128,
+                    amm_config.trade_fee_rate,
+                    amm_config.protocol_fee_rate,
+                    amm_config.fund_fee_rate
+                ).ok_or(SwapTokenError::InsufficientAmmLiquidity)?;
+                let amount_in = get_transfer_amount_with_fee(
+                    input_mint,
+                    swap.source_amount_swapped as u64
+                )?;
+
+                if amount_in > amm.other_amount_threshold {
+                    return err!(SwapTokenError::SlippageExceeded);
+                }
+
+                (amount_in, params.amount)
+            }
+            false => {
+                let swap = CurveCalculator::swap_base_input(
+                    (params.amount - get_transfer_fee(input_mint, params.amount)?) as u128,
+                    input_reserve as u128,
+                    output_reserve as u128,
+                    amm_config.trade_fee_rate,
+                    amm_config.protocol_fee_rate,
+                    amm_config.fund_fee_rate
+                ).ok_or(SwapTokenError::InsufficientAmmLiquidity)?;
+                let amount_out = swap.destination_amount_swapped as u64;
+                let amount_out = amount_out - get_transfer_fee(output_mint, amount_out)?;
+
+                if amount_out < amm.other_amount_threshold {
+                    return err!(SwapTokenError::SlippageExceeded);
+                }
+
+                (params.amount, amount_out)
+            }
+        };
+
+        let sell_tax = match params.trade_direction {
+            0 => 0,
+            _ => apply_basis_points(amount_out, self.bounding_curve.sell_tax_basis_points),
+        };
+        let (token_amount, pair_amount) = match params.trade_direction {
+            0 => (amount_out, amount_in),
+            _ => (amount_in, amount_out - sell_tax),
+        };
+
+        let mut result = SwapResult::new(token_amount, pair_amount, &SwapFees::default());
+        result.sell_tax = sell_tax;
+
+        Ok(result)
+    }
+}
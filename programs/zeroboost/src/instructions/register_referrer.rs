@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{ associated_token::AssociatedToken, token::{ Mint, Token, TokenAccount } };
+
+use crate::{ states::referral::{ Referral, REFERRAL_SIZE }, REFERRAL_SEED };
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    pair: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        seeds = [referrer.key().as_ref(), pair.key().as_ref(), REFERRAL_SEED.as_bytes()],
+        bump,
+        payer = referrer,
+        space = REFERRAL_SIZE
+    )]
+    referral: Box<Account<'info, Referral>>,
+    #[account(mut)]
+    referrer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        associated_token::mint = pair,
+        associated_token::authority = referrer
+    )]
+    referrer_pair_ata: Box<Account<'info, TokenAccount>>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> RegisterReferrer<'info> {
+    pub fn process_register_referrer(context: Context<RegisterReferrer>) -> Result<()> {
+        let referral = &mut context.accounts.referral;
+
+        referral.referrer = context.accounts.referrer.key();
+        referral.pair = context.accounts.pair.key();
+        referral.total_fees = 0;
+        referral.total_trades = 0;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    admin,
+    error::MintTokenError,
+    pyth,
+    states::pair_oracle::{ PairOracle, PAIR_ORACLE_SIZE },
+    PAIR_ORACLE_SEED,
+};
+
+#[derive(Accounts)]
+pub struct SetPairOracle<'info> {
+    pair: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        seeds = [pair.key().as_ref(), PAIR_ORACLE_SEED.as_bytes()],
+        bump,
+        space = PAIR_ORACLE_SIZE,
+        payer = admin
+    )]
+    pair_oracle: Box<Account<'info, PairOracle>>,
+    /// CHECK:
+    #[account(owner = pyth::ID @ MintTokenError::InvalidFeedAccount)]
+    pyth_pair_usd_feed: UncheckedAccount<'info>,
+    #[account(mut, address = admin::ID)]
+    admin: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> SetPairOracle<'info> {
+    pub fn process_set_pair_oracle(context: Context<SetPairOracle>) -> Result<()> {
+        let pair_oracle = &mut context.accounts.pair_oracle;
+
+        pair_oracle.pair = context.accounts.pair.key();
+        pair_oracle.feed = context.accounts.pyth_pair_usd_feed.key();
+
+        Ok(())
+    }
+}
@@ -1,37 +1,90 @@
-use anchor_lang::{ prelude::*, system_program::{ self, transfer } };
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::Instruction,
+        keccak::hashv,
+        program::{ get_return_data, invoke_signed },
+    },
+    system_program::{
+        self,
+        allocate,
+        assign,
+        create_account,
+        transfer,
+        Allocate,
+        Assign,
+        CreateAccount,
+    },
+    InstructionData,
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{
+        create_idempotent,
+        get_associated_token_address,
+        get_associated_token_address_with_program_id,
+        AssociatedToken,
+        Create,
+    },
     token::{
+        self,
+        close_account,
+        initialize_account3,
+        spl_token::native_mint,
         sync_native,
-        transfer_checked,
-        Mint,
+        CloseAccount,
+        InitializeAccount3,
         SyncNative,
         Token,
-        TokenAccount,
-        TransferChecked,
     },
+    token_interface::{ transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked },
+};
+use raydium_cp_swap::{
+    cpi::{ accounts::Swap as AmmSwap, swap_base_input, swap_base_output },
+    program::RaydiumCpSwap,
 };
 use curve::{
     curve::{ constant_curve::ConstantCurveCalculator, CurveCalculator, TradeDirection },
-    safe_number::safe_number::NewSafeNumber,
+    safe_number::SafeNumber,
 };
 
 use crate::{
     error::SwapTokenError,
-    events::{ SwapEvent, MigrateTriggerEvent },
-    states::{ bounding_curve::BoundingCurve, config::Config },
-    utils::Validate,
+    events::{ CircuitBreakerEvent, SellTaxEvent, SwapEvent, MigrateTriggerEvent },
+    migration_fee_receiver,
+    states::{
+        bounding_curve::BoundingCurve,
+        config::Config,
+        presale_claim::{ PresaleClaim, PRESALE_CLAIM_SIZE },
+        referral::Referral,
+        trader::{ Trader, TRADER_SIZE },
+    },
+    utils::{
+        apply_basis_points,
+        get_amm_pool_state_address,
+        get_transfer_amount_with_fee,
+        get_transfer_fee,
+        token_account_amount,
+        validate_mint_extensions,
+        verify_merkle_proof,
+        Validate,
+    },
+    BASIS_POINTS_DIVISOR,
+    BUYBACK_VAULT_SEED,
     CONFIG_SEED,
+    CREATOR_FEE_VAULT_SEED,
     CURVE_RESERVE_SEED,
     CURVE_SEED,
+    NATIVE_PAIR_SEED,
+    PRESALE_CLAIM_SEED,
+    TRADER_SEED,
 };
 
 #[derive(Accounts)]
 pub struct Swap<'info> {
     #[account(address = bounding_curve.mint)]
-    mint: Box<Account<'info, Mint>>,
+    mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(address = bounding_curve.pair)]
-    pair: Box<Account<'info, Mint>>,
+    pair: Box<InterfaceAccount<'info, Mint>>,
     #[account(seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
     config: Box<Account<'info, Config>>,
     #[account(
@@ -44,49 +97,523 @@ pub struct Swap<'info> {
     /// CHECK: bounding curve extra layer account for token reserve
     bounding_curve_reserve: AccountInfo<'info>,
     #[account(
-        init_if_needed,
-        payer = payer,
+        mut,
         associated_token::mint = mint,
-        associated_token::authority = bounding_curve_reserve
+        associated_token::authority = bounding_curve_reserve,
+        associated_token::token_program = mint_token_program
     )]
-    bounding_curve_reserve_ata: Box<Account<'info, TokenAccount>>,
+    bounding_curve_reserve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
-        init_if_needed,
-        payer = payer,
+        mut,
         associated_token::mint = pair,
         associated_token::authority = bounding_curve_reserve
     )]
-    bounding_curve_reserve_pair_ata: Box<Account<'info, TokenAccount>>,
+    bounding_curve_reserve_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), CREATOR_FEE_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    creator_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Protocol share of the swap fee is paid to the migration fee receiver
+    #[account(
+        mut,
+        address = get_associated_token_address(&migration_fee_receiver::ID, &pair.key())
+    )]
+    protocol_fee_pair_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [bounding_curve.key().as_ref(), BUYBACK_VAULT_SEED.as_bytes()],
+        bump
+    )]
+    buyback_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(mut)]
-    payer: Signer<'info>,
+    referral: Option<Box<Account<'info, Referral>>>,
+    #[account(mut)]
+    referrer_pair_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
         init_if_needed,
+        seeds = [bounding_curve.key().as_ref(), payer.key().as_ref(), TRADER_SEED.as_bytes()],
+        bump,
         payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = payer
+        space = TRADER_SIZE
     )]
-    payer_ata: Box<Account<'info, TokenAccount>>,
+    trader: Option<Box<Account<'info, Trader>>>,
     #[account(
         init_if_needed,
+        seeds = [bounding_curve.key().as_ref(), payer.key().as_ref(), PRESALE_CLAIM_SEED.as_bytes()],
+        bump,
         payer = payer,
-        associated_token::mint = pair,
-        associated_token::authority = payer
+        space = PRESALE_CLAIM_SIZE
+    )]
+    presale_claim: Option<Box<Account<'info, PresaleClaim>>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &payer.key(),
+            &mint.key(),
+            &mint_token_program.key()
+        )
     )]
-    payer_pair_ata: Box<Account<'info, TokenAccount>>,
+    /// CHECK: payer mint token account, only created when receiving tokens on buy
+    payer_ata: UncheckedAccount<'info>,
+    #[account(mut, address = get_associated_token_address(&payer.key(), &pair.key()))]
+    /// CHECK: payer pair token account, only created when receiving wrapped proceeds on sell
+    payer_pair_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: temporary wrapped pair account for native sells, validated against its seeds
+    payer_native_pair: Option<UncheckedAccount<'info>>,
+    /// CHECK: raydium cp pool accounts, only required once the curve migrated
+    amm_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by raydium
+    amm_authority: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated against the pool created on migration
+    amm_pool_state: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated by raydium
+    amm_mint_vault: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated by raydium
+    amm_pair_vault: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: validated by raydium
+    amm_observable_state: Option<UncheckedAccount<'info>>,
+    amm_program: Option<Program<'info, RaydiumCpSwap>>,
     system_program: Program<'info, System>,
+    mint_token_program: Interface<'info, TokenInterface>,
     token_program: Program<'info, Token>,
     associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[derive(Clone, Copy)]
+enum PairSource {
+    Lamports,
+    Payer,
+    Reserve,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct SwapFees {
+    pub total: u64,
+    pub creator: u64,
+    pub referral: u64,
+}
+
+impl SwapFees {
+    pub fn protocol(&self) -> u64 {
+        self.total - self.creator - self.referral
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct SwapResult {
+    pub token_amount: u64,
+    pub pair_amount: u64,
+    pub fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+    pub sell_tax: u64,
+    pub virtual_token_balance: u64,
+    pub virtual_pair_balance: u64,
+    pub migration_triggered: bool,
+}
+
+// Curve side of a trade before any account is touched, shared by swap and quote_swap
+#[derive(Clone, Copy, Default)]
+pub struct CurveQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fees: SwapFees,
+    pub sell_tax: u64,
+}
+
+// Scales the fee from the base towards the maximum as volatility approaches the reference level
+pub fn swap_fee_basis_points(config: &Config, bounding_curve: &BoundingCurve) -> u16 {
+    if
+        config.maximum_swap_fee_basis_points <= config.swap_fee_basis_points ||
+        config.volatility_reference_basis_points == 0
+    {
+        return config.swap_fee_basis_points;
+    }
+
+    let reference = config.volatility_reference_basis_points as u64;
+    let volatility = bounding_curve.volatility_accumulator.min(reference);
+    let spread = (config.maximum_swap_fee_basis_points - config.swap_fee_basis_points) as u64;
+
+    config.swap_fee_basis_points + ((spread * volatility) / reference) as u16
+}
+
+pub fn calculate_fees(
+    config: &Config,
+    bounding_curve: &BoundingCurve,
+    amount: u64,
+    referred: bool
+) -> SwapFees {
+    let total = apply_basis_points(amount, swap_fee_basis_points(config, bounding_curve));
+    let referral = match referred {
+        true => apply_basis_points(total, config.referral_fee_share_basis_points),
+        false => 0,
+    };
+
+    SwapFees {
+        total,
+        referral,
+        creator: apply_basis_points(total, config.creator_fee_share_basis_points),
+    }
+}
+
+// Buy amount is pair in including fees, amount in is what the curve actually prices
+pub fn quote_buy(
+    config: &Config,
+    bounding_curve: &BoundingCurve,
+    amount: u64,
+    referred: bool
+) -> CurveQuote {
+    let initial_price = SafeNumber::from(bounding_curve.initial_price);
+    let mut fees = calculate_fees(config, bounding_curve, amount, referred);
+
+    let mut amount_out = ConstantCurveCalculator::calculate_amount_out(
+        initial_price,
+        amount - fees.total,
+        TradeDirection::BtoA
+    );
+
+    // The buy that completes the curve takes whatever is left and only pays for that
+    let completes_curve = amount_out >= bounding_curve.virtual_token_balance;
+    if completes_curve {
+        amount_out = bounding_curve.virtual_token_balance;
+    }
+
+    let amount_in = ConstantCurveCalculator::calculate_amount_out(
+        initial_price,
+        amount_out,
+        TradeDirection::AtoB
+    );
+
+    if completes_curve {
+        fees = calculate_fees(config, bounding_curve, amount_in, referred);
+    }
+
+    CurveQuote { amount_in, amount_out, fees, sell_tax: 0 }
+}
+
+// Sell amount is tokens in, fees and sell tax are taken from the pair amount out
+pub fn quote_sell(
+    config: &Config,
+    bounding_curve: &BoundingCurve,
+    amount: u64,
+    referred: bool
+) -> CurveQuote {
+    let initial_price = SafeNumber::from(bounding_curve.initial_price);
+
+    let amount_out = ConstantCurveCalculator::calculate_amount_out(
+        initial_price,
+        amount,
+        TradeDirection::AtoB
+    );
+
+    let amount_in = ConstantCurveCalculator::calculate_amount_out(
+        initial_price,
+        amount_out,
+        TradeDirection::BtoA
+    );
+
+    CurveQuote {
+        amount_in,
+        amount_out,
+        fees: calculate_fees(config, bounding_curve, amount_out, referred),
+        sell_tax: apply_basis_points(amount_out, bounding_curve.sell_tax_basis_points),
+    }
+}
+
+pub fn validate_referral(
+    referral: Option<&Account<Referral>>,
+    referrer_pair_ata: Option<&InterfaceAccount<TokenAccount>>,
+    pair: Pubkey,
+    payer: Pubkey
+) -> Result<()> {
+    if let Some(referral) = referral {
+        let referrer_pair_ata = referrer_pair_ata.ok_or(SwapTokenError::InvalidReferral)?;
+
+        if
+            referral.pair != pair ||
+            referral.referrer == payer ||
+            referrer_pair_ata.owner != referral.referrer ||
+            referrer_pair_ata.mint != pair
+        {
+            return err!(SwapTokenError::InvalidReferral);
+        }
+    }
+    Ok(())
+}
+
+pub fn enforce_trading_start(bounding_curve: &BoundingCurve, now: i64) -> Result<()> {
+    if now < bounding_curve.trading_starts_at {
+        return err!(SwapTokenError::TradingNotStarted);
+    }
+
+    Ok(())
+}
+
+// Rejects trades while paused and opens a new measurement window once the previous one elapsed
+pub fn check_circuit_breaker(
+    config: &Config,
+    bounding_curve: &mut BoundingCurve,
+    slot: u64
+) -> Result<()> {
+    if slot < bounding_curve.paused_until_slot {
+        return err!(SwapTokenError::CircuitBreakerTripped);
+    }
+
+    let window_slots = slot.saturating_sub(bounding_curve.window_start_slot);
+
+    if window_slots >= config.circuit_breaker_window_slots {
+        bounding_curve.window_start_slot = slot;
+        bounding_curve.window_start_pair_balance = bounding_curve.virtual_pair_balance;
+    }
+
+    Ok(())
+}
+
+// Pauses the curve for the cooldown once the pair reserve moved past the threshold in the window
+pub fn update_circuit_breaker(config: &Config, bounding_curve: &mut BoundingCurve, clock: &Clock) {
+    let threshold = config.circuit_breaker_basis_points;
+
+    if threshold == 0 {
+        return;
+    }
+
+    let window_start_pair_balance = bounding_curve.window_start_pair_balance;
+    let change = bounding_curve.virtual_pair_balance.abs_diff(window_start_pair_balance);
+
+    if change < apply_basis_points(window_start_pair_balance, threshold) {
+        return;
+    }
+
+    bounding_curve.paused_until_slot = clock.slot + config.circuit_breaker_cooldown_slots;
+
+    emit!(CircuitBreakerEvent {
+        window_start_pair_balance,
+        mint: bounding_curve.mint,
+        virtual_pair_balance: bounding_curve.virtual_pair_balance,
+        paused_until_slot: bounding_curve.paused_until_slot,
+        timestamp: clock.unix_timestamp,
+    });
+}
+
+pub fn is_migration_reached(bounding_curve: &BoundingCurve, reserve_pair_balance: u64) -> bool {
+    reserve_pair_balance >= bounding_curve.maximum_pair_balance ||
+        bounding_curve.virtual_token_balance == 0
+}
+
+// Curve state every trade starts from, paused curves are rejected and volatility decays up to now
+pub fn prepare_curve_trade(
+    config: &Config,
+    bounding_curve: &mut BoundingCurve,
+    clock: &Clock
+) -> Result<()> {
+    check_circuit_breaker(config, bounding_curve, clock.slot)?;
+    bounding_curve.decay_volatility(clock.unix_timestamp, config.volatility_half_life);
+
+    Ok(())
+}
+
+// Curve state every trade leaves behind, the pair moved feeds the circuit breaker and volatility
+pub fn record_curve_trade(
+    config: &Config,
+    bounding_curve: &mut BoundingCurve,
+    pair_balance: u64,
+    pair_amount: u64,
+    clock: &Clock
+) {
+    update_circuit_breaker(config, bounding_curve, clock);
+
+    if pair_balance > 0 {
+        bounding_curve.accumulate_volatility(
+            ((pair_amount as u128) * (BASIS_POINTS_DIVISOR as u128) /
+                (pair_balance as u128)) as u64
+        );
+    }
+}
+
+// One off mutation, once the reserve holds the full pair balance or the curve sold out all curve
+// trading stops until the token is migrated to a dex, holders then keep trading through the pool
+pub fn trigger_migration(
+    bounding_curve: &mut BoundingCurve,
+    reserve_pair_balance: u64,
+    now: i64
+) -> bool {
+    if !is_migration_reached(bounding_curve, reserve_pair_balance) {
+        return false;
+    }
+
+    bounding_curve.tradeable = false;
+    emit!(MigrateTriggerEvent { mint: bounding_curve.mint, timestamp: now });
+
+    true
+}
+
+// Buys are capped per transaction and per wallet until the launch protection window ends
+pub fn enforce_launch_protection(
+    bounding_curve: &BoundingCurve,
+    trader: Option<&mut Trader>,
+    amount_out: u64,
+    now: i64
+) -> Result<()> {
+    if now >= bounding_curve.protection_ends_at {
+        return Ok(());
+    }
+
+    if amount_out > bounding_curve.protection_transaction_cap {
+        return err!(SwapTokenError::ProtectionTransactionCapExceeded);
+    }
+
+    let trader = trader.ok_or(SwapTokenError::MissingTraderAccount)?;
+    let protected_buy_amount = trader.protected_buy_amount + amount_out;
+
+    if protected_buy_amount > bounding_curve.protection_wallet_cap {
+        return err!(SwapTokenError::ProtectionWalletCapExceeded);
+    }
+
+    trader.protected_buy_amount = protected_buy_amount;
+
+    Ok(())
+}
+
+// Only allowlisted wallets can buy during the presale, each up to the presale wallet cap
+pub fn enforce_presale(
+    bounding_curve: &mut BoundingCurve,
+    presale_claim: Option<&mut PresaleClaim>,
+    wallet: Pubkey,
+    amount_out: u64,
+    proof: &Option<Vec<[u8; 32]>>,
+    now: i64
+) -> Result<()> {
+    if !bounding_curve.is_presale_active(now) {
+        return Ok(());
+    }
+
+    let proof = proof.as_ref().ok_or(SwapTokenError::NotAllowlisted)?;
+    let leaf = hashv(&[wallet.as_ref()]).0;
+
+    if !verify_merkle_proof(proof, bounding_curve.presale_root, leaf) {
+        return err!(SwapTokenError::NotAllowlisted);
+    }
+
+    let presale_claim = presale_claim.ok_or(SwapTokenError::MissingPresaleClaimAccount)?;
+    let claimed_amount = presale_claim.amount + amount_out;
+
+    if claimed_amount > bounding_curve.presale_wallet_cap {
+        return err!(SwapTokenError::PresaleWalletCapExceeded);
+    }
+
+    if bounding_curve.presale_sold + amount_out > bounding_curve.presale_token_cap {
+        return err!(SwapTokenError::PresaleCapExceeded);
+    }
+
+    presale_claim.amount = claimed_amount;
+    bounding_curve.presale_sold += amount_out;
+
+    Ok(())
+}
+
+// Records the buy slot so a restricted curve can reject a sell from the same wallet in that slot
+pub fn record_buy_slot(
+    bounding_curve: &BoundingCurve,
+    trader: Option<&mut Trader>,
+    slot: u64
+) -> Result<()> {
+    if !bounding_curve.same_slot_sell_restricted {
+        return Ok(());
+    }
+
+    let trader = trader.ok_or(SwapTokenError::MissingTraderAccount)?;
+    trader.last_buy_slot = slot;
+
+    Ok(())
+}
+
+pub fn enforce_same_slot_sell_restriction(
+    bounding_curve: &BoundingCurve,
+    trader: Option<&Trader>,
+    slot: u64
+) -> Result<()> {
+    if !bounding_curve.same_slot_sell_restricted {
+        return Ok(());
+    }
+
+    let trader = trader.ok_or(SwapTokenError::MissingTraderAccount)?;
+
+    if trader.last_buy_slot == slot {
+        return err!(SwapTokenError::SameSlotRoundTrip);
+    }
+
+    Ok(())
+}
+
+impl SwapResult {
+    pub fn new(token_amount: u64, pair_amount: u64, fees: &SwapFees) -> Self {
+        SwapResult {
+            token_amount,
+            pair_amount,
+            fee: fees.total,
+            creator_fee: fees.creator,
+            referral_fee: fees.referral,
+            ..Default::default()
+        }
+    }
+}
+
+// Raydium swap mode for migrated curves, base output treats amount as the exact amount out and the
+// threshold as the maximum amount in, otherwise amount is the exact amount in and the threshold the minimum out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct AmmSwapParams {
+    pub(crate) base_output: bool,
+    pub(crate) other_amount_threshold: u64,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Debug)]
 pub struct SwapParams {
-    amount: u64,
-    trade_direction: u8,
+    pub(crate) amount: u64,
+    pub(crate) trade_direction: u8,
+    pub(crate) native: Option<bool>,
+    pub(crate) amm: Option<AmmSwapParams>,
+    pub(crate) presale_proof: Option<Vec<[u8; 32]>>,
+}
+
+impl SwapParams {
+    pub fn new(amount: u64, trade_direction: u8, native: Option<bool>) -> Self {
+        SwapParams { amount, trade_direction, native, amm: None, presale_proof: None }
+    }
+}
+
+// Invokes swap on this program and reads the result back from return data, used by instructions
+// that trade on behalf of a signer. Missing optional accounts resolve to the program id, so the
+// program account must be part of the passed account infos
+pub fn invoke_swap<'info>(
+    accounts: crate::accounts::Swap,
+    account_infos: &[AccountInfo<'info>],
+    params: SwapParams,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<SwapResult> {
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: (crate::instruction::Swap { params }).data(),
+    };
+
+    invoke_signed(&instruction, account_infos, signer_seeds)?;
+
+    let (_, data) = get_return_data().ok_or(ErrorCode::InstructionDidNotDeserialize)?;
+    SwapResult::try_from_slice(&data).map_err(|_| error!(ErrorCode::InstructionDidNotDeserialize))
 }
 
 impl Validate for SwapParams {
     fn validate(&self) -> Result<()> {
-        if self.amount <= 0 {
+        if self.amount == 0 {
             return err!(SwapTokenError::InvalidAmount);
         }
         Ok(())
@@ -94,59 +621,534 @@ impl Validate for SwapParams {
 }
 
 impl<'info> Swap<'info> {
-    pub fn process_swap(context: Context<Swap>, params: &SwapParams) -> Result<()> {
+    pub fn process_swap(context: Context<Swap>, params: &SwapParams) -> Result<SwapResult> {
         params.validate()?;
+        validate_referral(
+            context.accounts.referral.as_deref(),
+            context.accounts.referrer_pair_ata.as_deref(),
+            context.accounts.pair.key(),
+            context.accounts.payer.key()
+        )?;
+        validate_mint_extensions(&context.accounts.mint.to_account_info())?;
+
+        if params.native.is_some() && !context.accounts.is_native_pair() {
+            return err!(SwapTokenError::InvalidNativePair);
+        }
+
+        // Migrated curves keep trading through the same entry point against the raydium pool
+        if context.accounts.bounding_curve.migrated {
+            let mut result = context.accounts.process_amm_swap(params)?;
+            result.virtual_token_balance = context.accounts.bounding_curve.virtual_token_balance;
+            result.virtual_pair_balance = context.accounts.bounding_curve.virtual_pair_balance;
+            context.accounts.emit_swap_event(params.trade_direction, &result)?;
+
+            return Ok(result);
+        }
 
         if !context.accounts.bounding_curve.tradeable {
             return err!(SwapTokenError::NotTradeable);
         }
 
+        let clock = Clock::get()?;
+        let accounts = &mut *context.accounts;
+        enforce_trading_start(&accounts.bounding_curve, clock.unix_timestamp)?;
+        prepare_curve_trade(&accounts.config, &mut accounts.bounding_curve, &clock)?;
+        let pair_balance = accounts.bounding_curve.virtual_pair_balance;
+
+        let bounding_curve_key = context.accounts.bounding_curve.key();
+        let payer_key = context.accounts.payer.key();
+        if let Some(trader) = &mut context.accounts.trader {
+            trader.bounding_curve = bounding_curve_key;
+            trader.wallet = payer_key;
+        }
+        if let Some(presale_claim) = &mut context.accounts.presale_claim {
+            presale_claim.bounding_curve = bounding_curve_key;
+            presale_claim.wallet = payer_key;
+        }
+
         let trade_direction = (match params.trade_direction {
             0 => Ok(TradeDirection::BtoA),
             1 => Ok(TradeDirection::AtoB),
             _ => err!(SwapTokenError::InvalidTradeDirection),
         })?;
 
-        let (token_amount, pair_amount) = (match trade_direction {
+        let mut result = (match trade_direction {
             TradeDirection::AtoB =>
-                context.accounts.process_sell(context.bumps.bounding_curve_reserve, &params),
+                context.accounts.process_sell(context.bumps.bounding_curve_reserve, params),
             TradeDirection::BtoA =>
-                context.accounts.process_buy(context.bumps.bounding_curve_reserve, &params),
+                context.accounts.process_buy(context.bumps.bounding_curve_reserve, params),
         })?;
 
+        result.virtual_token_balance = context.accounts.bounding_curve.virtual_token_balance;
+        result.virtual_pair_balance = context.accounts.bounding_curve.virtual_pair_balance;
+
+        let accounts = &mut *context.accounts;
+        record_curve_trade(
+            &accounts.config,
+            &mut accounts.bounding_curve,
+            pair_balance,
+            result.pair_amount,
+            &clock
+        );
+
+        context.accounts.emit_swap_event(params.trade_direction, &result)?;
+
+        Ok(result)
+    }
+
+    fn emit_swap_event(&self, trade_direction: u8, result: &SwapResult) -> Result<()> {
         let clock = Clock::get()?;
 
         emit!(SwapEvent {
-            token_amount,
-            pair_amount,
-            mint: context.accounts.mint.key(),
-            payer: context.accounts.payer.key(),
-            trade_direction: params.trade_direction,
-            virtual_token_balance: context.accounts.bounding_curve.virtual_token_balance,
-            virtual_pair_balance: context.accounts.bounding_curve.virtual_pair_balance,
-            market_cap: context.accounts.bounding_curve_reserve_pair_ata.amount,
+            trade_direction,
+            token_amount: result.token_amount,
+            pair_amount: result.pair_amount,
+            mint: self.mint.key(),
+            payer: self.payer.key(),
+            virtual_token_balance: result.virtual_token_balance,
+            virtual_pair_balance: result.virtual_pair_balance,
+            market_cap: self.bounding_curve_reserve_pair_ata.amount,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
+    fn is_native_pair(&self) -> bool {
+        self.pair.key() == native_mint::ID
+    }
+
+    fn transfer_pair(
+        &self,
+        source: PairSource,
+        to: AccountInfo<'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match source {
+            PairSource::Lamports => {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.payer.to_account_info(),
+                            to: to.clone(),
+                        }
+                    ),
+                    amount
+                )?;
+
+                sync_native(
+                    CpiContext::new(self.token_program.to_account_info(), SyncNative {
+                        account: to,
+                    })
+                )
+            }
+            PairSource::Payer =>
+                transfer_checked(
+                    CpiContext::new(self.token_program.to_account_info(), TransferChecked {
+                        to,
+                        mint: self.pair.to_account_info(),
+                        from: self.payer_pair_ata.to_account_info(),
+                        authority: self.payer.to_account_info(),
+                    }),
+                    amount,
+                    self.pair.decimals
+                ),
+            PairSource::Reserve =>
+                transfer_checked(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        TransferChecked {
+                            to,
+                            mint: self.pair.to_account_info(),
+                            from: self.bounding_curve_reserve_pair_ata.to_account_info(),
+                            authority: self.bounding_curve_reserve.to_account_info(),
+                        },
+                        signer_seeds
+                    ),
+                    amount,
+                    self.pair.decimals
+                ),
+        }
+    }
+
+    fn distribute_fees(
+        &mut self,
+        fees: &SwapFees,
+        source: PairSource,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<()> {
+        self.transfer_pair(
+            source,
+            self.creator_fee_vault.to_account_info(),
+            fees.creator,
+            signer_seeds
+        )?;
+
+        if let Some(referrer_pair_ata) = &self.referrer_pair_ata {
+            self.transfer_pair(
+                source,
+                referrer_pair_ata.to_account_info(),
+                fees.referral,
+                signer_seeds
+            )?;
+        }
+
+        self.transfer_pair(
+            source,
+            self.protocol_fee_pair_ata.to_account_info(),
+            fees.protocol(),
+            signer_seeds
+        )?;
+
+        if let Some(referral) = &mut self.referral {
+            referral.total_fees += fees.referral;
+            referral.total_trades += 1;
+        }
+
+        Ok(())
+    }
+
+    // Sell tax accumulates in the buyback vault until it is spent by buyback_and_burn
+    fn collect_sell_tax(
+        &self,
+        from: AccountInfo<'info>,
+        authority: AccountInfo<'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from,
+                    authority,
+                    mint: self.pair.to_account_info(),
+                    to: self.buyback_vault.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            self.pair.decimals
+        )?;
+
+        let clock = Clock::get()?;
+
+        emit!(SellTaxEvent {
+            amount,
+            mint: self.mint.key(),
+            payer: self.payer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn create_payer_ata(
+        &self,
+        associated_token: AccountInfo<'info>,
+        mint: AccountInfo<'info>,
+        token_program: AccountInfo<'info>
+    ) -> Result<()> {
+        create_idempotent(
+            CpiContext::new(self.associated_token_program.to_account_info(), Create {
+                mint,
+                associated_token,
+                token_program,
+                payer: self.payer.to_account_info(),
+                authority: self.payer.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            })
+        )
+    }
+
+    // Creates a wrapped pair account owned by the payer, the account is closed
+    // back into the payer as lamports once proceeds are paid into it
+    fn create_payer_native_pair(&self) -> Result<AccountInfo<'info>> {
+        let payer_native_pair = self.payer_native_pair
+            .as_ref()
+            .ok_or(SwapTokenError::InvalidNativePair)?;
+
+        let payer_key = self.payer.key();
+        let (payer_native_pair_key, bump) = Pubkey::find_program_address(
+            &[payer_key.as_ref(), NATIVE_PAIR_SEED.as_bytes()],
+            &crate::ID
+        );
+
+        if payer_native_pair.key() != payer_native_pair_key {
+            return err!(SwapTokenError::InvalidNativePair);
+        }
+
+        let signer_seeds = &[payer_key.as_ref(), NATIVE_PAIR_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&signer_seeds[..]];
+        let rent = Rent::get()?.minimum_balance(token::TokenAccount::LEN);
+        let lamports = payer_native_pair.lamports();
+
+        // The address is deterministic, so anyone can pre-fund it and make create_account fail,
+        // a funded address is topped up to rent and then allocated and assigned in place
+        if lamports == 0 {
+            create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    CreateAccount {
+                        from: self.payer.to_account_info(),
+                        to: payer_native_pair.to_account_info(),
+                    },
+                    signer_seeds
+                ),
+                rent,
+                token::TokenAccount::LEN as u64,
+                &self.token_program.key()
+            )?;
+        } else {
+            if rent > lamports {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.payer.to_account_info(),
+                            to: payer_native_pair.to_account_info(),
+                        }
+                    ),
+                    rent - lamports
+                )?;
+            }
+
+            allocate(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Allocate { account_to_allocate: payer_native_pair.to_account_info() },
+                    signer_seeds
+                ),
+                token::TokenAccount::LEN as u64
+            )?;
+
+            assign(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Assign { account_to_assign: payer_native_pair.to_account_info() },
+                    signer_seeds
+                ),
+                &self.token_program.key()
+            )?;
+        }
+
+        initialize_account3(
+            CpiContext::new(self.token_program.to_account_info(), InitializeAccount3 {
+                account: payer_native_pair.to_account_info(),
+                mint: self.pair.to_account_info(),
+                authority: self.payer.to_account_info(),
+            })
+        )?;
+
+        Ok(payer_native_pair.to_account_info())
+    }
+
     #[inline(never)]
-    fn process_buy(&mut self, curve_bump: u8, params: &SwapParams) -> Result<(u64, u64)> {
-        let bounding_curve = &mut self.bounding_curve;
-        let initial_price = f64::new(bounding_curve.initial_price);
+    fn process_amm_swap(&self, params: &SwapParams) -> Result<SwapResult> {
+        let (
+            Some(amm_config),
+            Some(amm_authority),
+            Some(amm_pool_state),
+            Some(amm_mint_vault),
+            Some(amm_pair_vault),
+            Some(amm_observable_state),
+            Some(amm_program),
+        ) = (
+            &self.amm_config,
+            &self.amm_authority,
+            &self.amm_pool_state,
+            &self.amm_mint_vault,
+            &self.amm_pair_vault,
+            &self.amm_observable_state,
+            &self.amm_program,
+        ) else {
+            return err!(SwapTokenError::MissingAmmAccounts);
+        };
+
+        let amm_pool_state_address = get_amm_pool_state_address(
+            &amm_config.key(),
+            self.mint.key(),
+            self.pair.key(),
+            &amm_program.key()
+        );
+
+        if amm_pool_state.key() != amm_pool_state_address {
+            return err!(SwapTokenError::InvalidAmmPool);
+        }
+
+        // No default threshold, a zero minimum out would let the pool trade at any price
+        let amm = params.amm.ok_or(SwapTokenError::MissingAmmSwapParams)?;
+        let amount_in = match amm.base_output {
+            true => amm.other_amount_threshold,
+            false => params.amount,
+        };
+
+        // Native settlement goes through the temporary wrapped pair account like curve trades,
+        // buys wrap the maximum amount in and any unspent lamports are returned when it is closed
+        let (native, input_token_account, output_token_account, input_vault, output_vault) = match
+            params.trade_direction
+        {
+            0 => {
+                let native = self.is_native_pair() && params.native.unwrap_or(true);
+                let input_token_account = match native {
+                    true => {
+                        let payer_native_pair = self.create_payer_native_pair()?;
+                        self.transfer_pair(
+                            PairSource::Lamports,
+                            payer_native_pair.clone(),
+                            amount_in,
+                            &[]
+                        )?;
+                        payer_native_pair
+                    }
+                    false => self.payer_pair_ata.to_account_info(),
+                };
+                self.create_payer_ata(
+                    self.payer_ata.to_account_info(),
+                    self.mint.to_account_info(),
+                    self.mint_token_program.to_account_info()
+                )?;
+
+                (
+                    native,
+                    input_token_account,
+                    self.payer_ata.to_account_info(),
+                    amm_pair_vault.to_account_info(),
+                    amm_mint_vault.to_account_info(),
+                )
+            }
+            1 => {
+                let native = params.native.unwrap_or(false);
+                let output_token_account = match native {
+                    true => self.create_payer_native_pair()?,
+                    false => {
+                        self.create_payer_ata(
+                            self.payer_pair_ata.to_account_info(),
+                            self.pair.to_account_info(),
+                            self.token_program.to_account_info()
+                        )?;
+                        self.payer_pair_ata.to_account_info()
+                    }
+                };
+
+                (
+                    native,
+                    self.payer_ata.to_account_info(),
+                    output_token_account,
+                    amm_mint_vault.to_account_info(),
+                    amm_pair_vault.to_account_info(),
+                )
+            }
+            _ => {
+                return err!(SwapTokenError::InvalidTradeDirection);
+            }
+        };
+
+        let (input_token_mint, output_token_mint) = match params.trade_direction {
+            0 => (self.pair.to_account_info(), self.mint.to_account_info()),
+            _ => (self.mint.to_account_info(), self.pair.to_account_info()),
+        };
+        let (input_token_program, output_token_program) = match params.trade_direction {
+            0 => (self.token_program.to_account_info(), self.mint_token_program.to_account_info()),
+            _ => (self.mint_token_program.to_account_info(), self.token_program.to_account_info()),
+        };
 
-        let amount_out = ConstantCurveCalculator::calculate_amount_out(
-            initial_price,
+        let input_balance = token_account_amount(&input_token_account)?;
+        let output_balance = token_account_amount(&output_token_account)?;
+
+        let context = CpiContext::new(amm_program.to_account_info(), AmmSwap {
+            input_vault,
+            output_vault,
+            input_token_mint,
+            output_token_mint,
+            payer: self.payer.to_account_info(),
+            authority: amm_authority.to_account_info(),
+            amm_config: amm_config.to_account_info(),
+            pool_state: amm_pool_state.to_account_info(),
+            input_token_account: input_token_account.clone(),
+            output_token_account: output_token_account.clone(),
+            input_token_program,
+            output_token_program,
+            observation_state: amm_observable_state.to_account_info(),
+        });
+
+        match amm.base_output {
+            true => swap_base_output(context, amm.other_amount_threshold, params.amount)?,
+            false => swap_base_input(context, params.amount, amm.other_amount_threshold)?,
+        }
+
+        let amount_in = input_balance - token_account_amount(&input_token_account)?;
+        let amount_out = token_account_amount(&output_token_account)? - output_balance;
+
+        let sell_tax = match params.trade_direction {
+            0 => 0,
+            _ => apply_basis_points(amount_out, self.bounding_curve.sell_tax_basis_points),
+        };
+        self.collect_sell_tax(
+            output_token_account.clone(),
+            self.payer.to_account_info(),
+            sell_tax,
+            &[]
+        )?;
+
+        if native {
+            let payer_native_pair = match params.trade_direction {
+                0 => input_token_account,
+                _ => output_token_account,
+            };
+
+            close_account(
+                CpiContext::new(self.token_program.to_account_info(), CloseAccount {
+                    account: payer_native_pair,
+                    destination: self.payer.to_account_info(),
+                    authority: self.payer.to_account_info(),
+                })
+            )?;
+        }
+
+        let (token_amount, pair_amount) = match params.trade_direction {
+            0 => (amount_out, amount_in),
+            _ => (amount_in, amount_out - sell_tax),
+        };
+
+        let mut result = SwapResult::new(token_amount, pair_amount, &SwapFees::default());
+        result.sell_tax = sell_tax;
+
+        Ok(result)
+    }
+
+    #[inline(never)]
+    fn process_buy(&mut self, curve_bump: u8, params: &SwapParams) -> Result<SwapResult> {
+        let CurveQuote { amount_in, amount_out, fees, .. } = quote_buy(
+            &self.config,
+            &self.bounding_curve,
             params.amount,
-            TradeDirection::BtoA
+            self.referral.is_some()
         );
 
-        let amount_in = ConstantCurveCalculator::calculate_amount_out(
-            initial_price,
+        let clock = Clock::get()?;
+        let trader = self.trader.as_deref_mut().map(|trader| &mut **trader);
+        enforce_launch_protection(&self.bounding_curve, trader, amount_out, clock.unix_timestamp)?;
+        enforce_presale(
+            &mut self.bounding_curve,
+            self.presale_claim.as_deref_mut().map(|presale_claim| &mut **presale_claim),
+            self.payer.key(),
             amount_out,
-            TradeDirection::AtoB
-        );
+            &params.presale_proof,
+            clock.unix_timestamp
+        )?;
+        let trader = self.trader.as_deref_mut().map(|trader| &mut **trader);
+        record_buy_slot(&self.bounding_curve, trader, clock.slot)?;
 
         let bounding_curve_key = self.bounding_curve.key();
         let signer_seeds = &[
@@ -156,17 +1158,31 @@ impl<'info> Swap<'info> {
         ];
         let signer_seeds = &[&signer_seeds[..]];
 
-        transfer(
-            CpiContext::new(self.token_program.to_account_info(), system_program::Transfer {
-                from: self.payer.to_account_info(),
-                to: self.bounding_curve_reserve_pair_ata.to_account_info(),
-            }),
-            amount_in
+        // Native pair buys are funded with lamports unless the trader opts to spend wrapped balance,
+        // any other pair is always paid from the trader pair token account
+        let source = match self.is_native_pair() && params.native.unwrap_or(true) {
+            true => PairSource::Lamports,
+            false => PairSource::Payer,
+        };
+
+        self.transfer_pair(
+            source,
+            self.bounding_curve_reserve_pair_ata.to_account_info(),
+            amount_in,
+            signer_seeds
+        )?;
+
+        self.distribute_fees(&fees, source, signer_seeds)?;
+
+        self.create_payer_ata(
+            self.payer_ata.to_account_info(),
+            self.mint.to_account_info(),
+            self.mint_token_program.to_account_info()
         )?;
 
         transfer_checked(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
+                self.mint_token_program.to_account_info(),
                 TransferChecked {
                     mint: self.mint.to_account_info(),
                     from: self.bounding_curve_reserve_ata.to_account_info(),
@@ -181,43 +1197,38 @@ impl<'info> Swap<'info> {
 
         self.bounding_curve.add(self.pair.key(), amount_in);
         self.bounding_curve.sub(self.mint.key(), amount_out);
+        self.bounding_curve.traded = true;
 
-        sync_native(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                SyncNative {
-                    account: self.bounding_curve_reserve_pair_ata.to_account_info(),
-                },
-                signer_seeds
-            )
-        )?;
+        // Reserve pays out the full amount, transfer fee mints withhold part of it from the payer
+        let amount_received = amount_out - get_transfer_fee(&self.mint.to_account_info(), amount_out)?;
 
-        // One off mutation, If trade is maked as non tradeable all swap is stop until token migrated to a dex
-        // when migrated token holders can continue trade with dex
-        if self.bounding_curve.maximum_pair_balance >= self.bounding_curve_reserve_pair_ata.amount {
-            self.bounding_curve.tradeable = false;
-            let clock = Clock::get()?;
-            emit!(MigrateTriggerEvent { mint: self.mint.key(), timestamp: clock.unix_timestamp });
-        }
+        let mut result = SwapResult::new(amount_received, amount_in + fees.total, &fees);
 
-        Ok((amount_out, amount_in))
+        self.bounding_curve_reserve_pair_ata.reload()?;
+        result.migration_triggered = trigger_migration(
+            &mut self.bounding_curve,
+            self.bounding_curve_reserve_pair_ata.amount,
+            clock.unix_timestamp
+        );
+
+        Ok(result)
     }
 
     #[inline(never)]
-    fn process_sell(&mut self, curve_bump: u8, params: &SwapParams) -> Result<(u64, u64)> {
-        let initial_price = f64::new(self.bounding_curve.initial_price);
-
-        let amount_out = ConstantCurveCalculator::calculate_amount_out(
-            initial_price,
-            params.amount,
-            TradeDirection::AtoB
-        );
+    fn process_sell(&mut self, curve_bump: u8, params: &SwapParams) -> Result<SwapResult> {
+        let clock = Clock::get()?;
+        let trader = self.trader.as_deref().map(|trader| &**trader);
+        enforce_same_slot_sell_restriction(&self.bounding_curve, trader, clock.slot)?;
 
-        let amount_in = ConstantCurveCalculator::calculate_amount_out(
-            initial_price,
-            amount_out,
-            TradeDirection::BtoA
+        // Curve is priced on what the reserve receives once the transfer fee is withheld
+        let transfer_fee = get_transfer_fee(&self.mint.to_account_info(), params.amount)?;
+        let CurveQuote { amount_in, amount_out, fees, sell_tax } = quote_sell(
+            &self.config,
+            &self.bounding_curve,
+            params.amount - transfer_fee,
+            self.referral.is_some()
         );
+        let amount_sent = get_transfer_amount_with_fee(&self.mint.to_account_info(), amount_in)?;
 
         let bounding_curve_key = self.bounding_curve.key();
         let signer_seeds = &[
@@ -228,40 +1239,69 @@ impl<'info> Swap<'info> {
         let signer_seeds = &[&signer_seeds[..]];
 
         transfer_checked(
-            CpiContext::new(self.token_program.to_account_info(), TransferChecked {
+            CpiContext::new(self.mint_token_program.to_account_info(), TransferChecked {
                 mint: self.mint.to_account_info(),
                 from: self.payer_ata.to_account_info(),
                 to: self.bounding_curve_reserve_ata.to_account_info(),
                 authority: self.payer.to_account_info(),
             }),
-            amount_in,
+            amount_sent,
             self.mint.decimals
         )?;
 
-        transfer_checked(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                TransferChecked {
-                    mint: self.pair.to_account_info(),
-                    to: self.payer_pair_ata.to_account_info(),
-                    from: self.bounding_curve_reserve_pair_ata.to_account_info(),
-                    authority: self.bounding_curve_reserve.to_account_info(),
-                },
-                signer_seeds
-            ),
-            amount_out,
-            self.pair.decimals
+        // Sell proceeds stay wrapped unless the trader opts to receive lamports
+        let native = params.native.unwrap_or(false);
+        let proceeds_account = match native {
+            true => self.create_payer_native_pair()?,
+            false => {
+                self.create_payer_ata(
+                    self.payer_pair_ata.to_account_info(),
+                    self.pair.to_account_info(),
+                    self.token_program.to_account_info()
+                )?;
+                self.payer_pair_ata.to_account_info()
+            }
+        };
+
+        self.transfer_pair(
+            PairSource::Reserve,
+            proceeds_account.clone(),
+            amount_out - fees.total - sell_tax,
+            signer_seeds
+        )?;
+
+        self.distribute_fees(&fees, PairSource::Reserve, signer_seeds)?;
+
+        self.collect_sell_tax(
+            self.bounding_curve_reserve_pair_ata.to_account_info(),
+            self.bounding_curve_reserve.to_account_info(),
+            sell_tax,
+            signer_seeds
         )?;
 
         self.bounding_curve.sub(self.pair.key(), amount_out);
         self.bounding_curve.add(self.mint.key(), amount_in);
+        self.bounding_curve.traded = true;
 
-        sync_native(
-            CpiContext::new(self.token_program.to_account_info(), SyncNative {
-                account: self.payer_pair_ata.to_account_info(),
-            })
-        )?;
+        if native {
+            close_account(
+                CpiContext::new(self.token_program.to_account_info(), CloseAccount {
+                    account: proceeds_account,
+                    destination: self.payer.to_account_info(),
+                    authority: self.payer.to_account_info(),
+                })
+            )?;
+        } else if self.is_native_pair() {
+            sync_native(
+                CpiContext::new(self.token_program.to_account_info(), SyncNative {
+                    account: self.payer_pair_ata.to_account_info(),
+                })
+            )?;
+        }
+
+        let mut result = SwapResult::new(amount_sent, amount_out - fees.total - sell_tax, &fees);
+        result.sell_tax = sell_tax;
 
-        Ok((amount_in, amount_out))
+        Ok(result)
     }
 }
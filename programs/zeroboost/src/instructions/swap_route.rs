@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ spl_token::native_mint, Token },
+    token_interface::{ Mint, TokenInterface },
+};
+
+use crate::{
+    error::SwapTokenError,
+    instructions::{ invoke_swap, SwapParams, SwapResult },
+    program::Zeroboost,
+    states::bounding_curve::BoundingCurve,
+    utils::Validate,
+};
+
+#[derive(Accounts)]
+pub struct SwapRoute<'info> {
+    #[account(address = source_bounding_curve.mint)]
+    source_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = destination_bounding_curve.mint)]
+    destination_mint: Box<InterfaceAccount<'info, Mint>>,
+    pair: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: validated by swap
+    config: UncheckedAccount<'info>,
+    #[account(mut, has_one = pair)]
+    source_bounding_curve: Box<Account<'info, BoundingCurve>>,
+    /// CHECK: validated by swap
+    source_bounding_curve_reserve: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    source_bounding_curve_reserve_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    source_bounding_curve_reserve_pair_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    source_creator_fee_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    source_buyback_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap, required when the source curve restricts same slot sells
+    source_trader: Option<UncheckedAccount<'info>>,
+    #[account(mut, has_one = pair)]
+    destination_bounding_curve: Box<Account<'info, BoundingCurve>>,
+    /// CHECK: validated by swap
+    destination_bounding_curve_reserve: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    destination_bounding_curve_reserve_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    destination_bounding_curve_reserve_pair_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    destination_creator_fee_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    destination_buyback_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap, required while the destination curve is launch protected
+    destination_trader: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    payer_source_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    payer_destination_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap
+    payer_pair_ata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: validated by swap, both legs trade the same pair
+    protocol_fee_pair_ata: UncheckedAccount<'info>,
+    zeroboost_program: Program<'info, Zeroboost>,
+    system_program: Program<'info, System>,
+    #[account(address = *source_mint.to_account_info().owner)]
+    source_mint_token_program: Interface<'info, TokenInterface>,
+    #[account(address = *destination_mint.to_account_info().owner)]
+    destination_mint_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct SwapRouteParams {
+    amount: u64,
+    minimum_amount_out: u64,
+}
+
+impl Validate for SwapRouteParams {
+    fn validate(&self) -> Result<()> {
+        if self.amount == 0 {
+            return err!(SwapTokenError::InvalidAmount);
+        }
+        Ok(())
+    }
+}
+
+impl<'info> SwapRoute<'info> {
+    pub fn process_swap_route(
+        context: Context<SwapRoute>,
+        params: &SwapRouteParams
+    ) -> Result<SwapResult> {
+        params.validate()?;
+
+        let accounts = &mut *context.accounts;
+
+        if accounts.source_mint.key() == accounts.destination_mint.key() {
+            return err!(SwapTokenError::InvalidRoute);
+        }
+
+        // Sell proceeds stay wrapped in the payer pair account and fund the buy leg
+        let sell = accounts.sell(params.amount)?;
+        let buy = accounts.buy(sell.pair_amount)?;
+
+        // Swaps wrote both curves, the cached copies are refreshed so they aren't written back stale
+        accounts.source_bounding_curve.reload()?;
+        accounts.destination_bounding_curve.reload()?;
+
+        if buy.token_amount < params.minimum_amount_out {
+            return err!(SwapTokenError::SlippageExceeded);
+        }
+
+        Ok(buy)
+    }
+
+    fn sell(&self, amount: u64) -> Result<SwapResult> {
+        let swap_accounts = crate::accounts::Swap {
+            mint: self.source_mint.key(),
+            pair: self.pair.key(),
+            config: self.config.key(),
+            bounding_curve: self.source_bounding_curve.key(),
+            bounding_curve_reserve: self.source_bounding_curve_reserve.key(),
+            bounding_curve_reserve_ata: self.source_bounding_curve_reserve_ata.key(),
+            bounding_curve_reserve_pair_ata: self.source_bounding_curve_reserve_pair_ata.key(),
+            creator_fee_vault: self.source_creator_fee_vault.key(),
+            protocol_fee_pair_ata: self.protocol_fee_pair_ata.key(),
+            buyback_vault: self.source_buyback_vault.key(),
+            referral: None,
+            referrer_pair_ata: None,
+            trader: self.source_trader.as_ref().map(|trader| trader.key()),
+            presale_claim: None,
+            payer: self.payer.key(),
+            payer_ata: self.payer_source_ata.key(),
+            payer_pair_ata: self.payer_pair_ata.key(),
+            payer_native_pair: None,
+            amm_config: None,
+            amm_authority: None,
+            amm_pool_state: None,
+            amm_mint_vault: None,
+            amm_pair_vault: None,
+            amm_observable_state: None,
+            amm_program: None,
+            system_program: self.system_program.key(),
+            mint_token_program: self.source_mint_token_program.key(),
+            token_program: self.token_program.key(),
+            associated_token_program: self.associated_token_program.key(),
+        };
+
+        let mut account_infos = vec![
+            self.source_mint.to_account_info(),
+            self.pair.to_account_info(),
+            self.config.to_account_info(),
+            self.source_bounding_curve.to_account_info(),
+            self.source_bounding_curve_reserve.to_account_info(),
+            self.source_bounding_curve_reserve_ata.to_account_info(),
+            self.source_bounding_curve_reserve_pair_ata.to_account_info(),
+            self.source_creator_fee_vault.to_account_info(),
+            self.protocol_fee_pair_ata.to_account_info(),
+            self.source_buyback_vault.to_account_info(),
+            self.payer.to_account_info(),
+            self.payer_source_ata.to_account_info(),
+            self.payer_pair_ata.to_account_info(),
+            self.zeroboost_program.to_account_info(),
+            self.system_program.to_account_info(),
+            self.source_mint_token_program.to_account_info(),
+            self.token_program.to_account_info(),
+            self.associated_token_program.to_account_info(),
+        ];
+        if let Some(source_trader) = &self.source_trader {
+            account_infos.push(source_trader.to_account_info());
+        }
+
+        invoke_swap(swap_accounts, &account_infos, SwapParams::new(amount, 1, None), &[])
+    }
+
+    fn buy(&self, amount: u64) -> Result<SwapResult> {
+        // Native pair buys spend the wrapped sell proceeds instead of lamports
+        let native = match self.pair.key() == native_mint::ID {
+            true => Some(false),
+            false => None,
+        };
+
+        let swap_accounts = crate::accounts::Swap {
+            mint: self.destination_mint.key(),
+            pair: self.pair.key(),
+            config: self.config.key(),
+            bounding_curve: self.destination_bounding_curve.key(),
+            bounding_curve_reserve: self.destination_bounding_curve_reserve.key(),
+            bounding_curve_reserve_ata: self.destination_bounding_curve_reserve_ata.key(),
+            bounding_curve_reserve_pair_ata: self.destination_bounding_curve_reserve_pair_ata.key(),
+            creator_fee_vault: self.destination_creator_fee_vault.key(),
+            protocol_fee_pair_ata: self.protocol_fee_pair_ata.key(),
+            buyback_vault: self.destination_buyback_vault.key(),
+            referral: None,
+            referrer_pair_ata: None,
+            trader: self.destination_trader.as_ref().map(|trader| trader.key()),
+            presale_claim: None,
+            payer: self.payer.key(),
+            payer_ata: self.payer_destination_ata.key(),
+            payer_pair_ata: self.payer_pair_ata.key(),
+            payer_native_pair: None,
+            amm_config: None,
+            amm_authority: None,
+            amm_pool_state: None,
+            amm_mint_vault: None,
+            amm_pair_vault: None,
+            amm_observable_state: None,
+            amm_program: None,
+            system_program: self.system_program.key(),
+            mint_token_program: self.destination_mint_token_program.key(),
+            token_program: self.token_program.key(),
+            associated_token_program: self.associated_token_program.key(),
+        };
+
+        let mut account_infos = vec![
+            self.destination_mint.to_account_info(),
+            self.pair.to_account_info(),
+            self.config.to_account_info(),
+            self.destination_bounding_curve.to_account_info(),
+            self.destination_bounding_curve_reserve.to_account_info(),
+            self.destination_bounding_curve_reserve_ata.to_account_info(),
+            self.destination_bounding_curve_reserve_pair_ata.to_account_info(),
+            self.destination_creator_fee_vault.to_account_info(),
+            self.protocol_fee_pair_ata.to_account_info(),
+            self.destination_buyback_vault.to_account_info(),
+            self.payer.to_account_info(),
+            self.payer_destination_ata.to_account_info(),
+            self.payer_pair_ata.to_account_info(),
+            self.zeroboost_program.to_account_info(),
+            self.system_program.to_account_info(),
+            self.destination_mint_token_program.to_account_info(),
+            self.token_program.to_account_info(),
+            self.associated_token_program.to_account_info(),
+        ];
+        if let Some(destination_trader) = &self.destination_trader {
+            account_infos.push(destination_trader.to_account_info());
+        }
+
+        invoke_swap(swap_accounts, &account_infos, SwapParams::new(amount, 0, native), &[])
+    }
+}
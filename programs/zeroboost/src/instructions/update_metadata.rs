@@ -0,0 +1,213 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::program::invoke_signed,
+    system_program::{ transfer, Transfer },
+};
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::types::DataV2,
+        update_metadata_accounts_v2,
+        Metadata,
+        MetadataAccount,
+        UpdateMetadataAccountsV2,
+    },
+    token_2022::{
+        self,
+        spl_token_2022::{
+            extension::{ BaseStateWithExtensions, StateWithExtensions },
+            state::Mint as Token2022Mint,
+        },
+    },
+    token_interface::{ Mint, TokenInterface },
+};
+use spl_token_metadata_interface::{
+    instruction::update_field,
+    state::{ Field, TokenMetadata },
+};
+
+use crate::{
+    error::UpdateMetadataError,
+    events::UpdateMetadataEvent,
+    states::bounding_curve::BoundingCurve,
+    CURVE_SEED,
+};
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut, address = bounding_curve.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(seeds = [mint.key().as_ref(), CURVE_SEED.as_bytes()], bump, has_one = creator)]
+    bounding_curve: Box<Account<'info, BoundingCurve>>,
+    #[account(
+        mut,
+        seeds = [b"metadata", anchor_spl::metadata::ID.as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = anchor_spl::metadata::ID
+    )]
+    /// CHECK: metaplex metadata account, only used by legacy token mints
+    metadata: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    creator: Signer<'info>,
+    token_metadata_program: Option<Program<'info, Metadata>>,
+    mint_token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateMetadataParams {
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+}
+
+impl<'info> UpdateMetadata<'info> {
+    pub fn process_update_metadata(
+        context: Context<UpdateMetadata>,
+        params: &UpdateMetadataParams
+    ) -> Result<()> {
+        let accounts = &context.accounts;
+
+        // Metadata is frozen once the curve graduates
+        if accounts.bounding_curve.migrated {
+            return err!(UpdateMetadataError::AlreadyMigrated);
+        }
+
+        let mint_key = accounts.mint.key();
+        let signer_seeds = &[
+            mint_key.as_ref(),
+            CURVE_SEED.as_bytes(),
+            &[context.bumps.bounding_curve],
+        ];
+        let signer_seeds = &[&signer_seeds[..]];
+
+        let (name, symbol, uri) = match accounts.mint_token_program.key() == token_2022::ID {
+            true => accounts.update_token_metadata(params, signer_seeds)?,
+            false => accounts.update_metaplex_metadata(params, signer_seeds)?,
+        };
+
+        let clock = Clock::get()?;
+
+        emit!(UpdateMetadataEvent {
+            name,
+            symbol,
+            uri,
+            mint: mint_key,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn update_metaplex_metadata(
+        &self,
+        params: &UpdateMetadataParams,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<(String, String, String)> {
+        let (Some(metadata), Some(token_metadata_program)) = (
+            &self.metadata,
+            &self.token_metadata_program,
+        ) else {
+            return err!(UpdateMetadataError::MissingMetadataAccount);
+        };
+
+        let current = MetadataAccount::try_deserialize(&mut &metadata.try_borrow_data()?[..])?;
+
+        // Metaplex pads stored strings with null bytes
+        let name = params.name.clone().unwrap_or(current.name.trim_end_matches('\0').to_string());
+        let symbol = params.symbol
+            .clone()
+            .unwrap_or(current.symbol.trim_end_matches('\0').to_string());
+        let uri = params.uri.clone().unwrap_or(current.uri.trim_end_matches('\0').to_string());
+
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: metadata.to_account_info(),
+                    update_authority: self.bounding_curve.to_account_info(),
+                },
+                signer_seeds
+            ),
+            None,
+            Some(DataV2 {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points: current.seller_fee_basis_points,
+                creators: current.creators.clone(),
+                collection: current.collection.clone(),
+                uses: current.uses.clone(),
+            }),
+            None,
+            None
+        )?;
+
+        Ok((name, symbol, uri))
+    }
+
+    // Token metadata lives on the mint, which is reallocated on update so rent is topped up first
+    fn update_token_metadata(
+        &self,
+        params: &UpdateMetadataParams,
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<(String, String, String)> {
+        let mint_info = self.mint.to_account_info();
+        let (current, data_len) = {
+            let data = mint_info.try_borrow_data()?;
+            let state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+            (state.get_variable_len_extension::<TokenMetadata>()?, data.len())
+        };
+
+        let fields = [
+            (Field::Name, &params.name, current.name.len()),
+            (Field::Symbol, &params.symbol, current.symbol.len()),
+            (Field::Uri, &params.uri, current.uri.len()),
+        ];
+
+        let updated_len = fields
+            .iter()
+            .fold(data_len, |len, (_, value, current_len)| {
+                match value {
+                    Some(value) => len + value.len() - current_len,
+                    None => len,
+                }
+            });
+        let minimum_balance = Rent::get()?.minimum_balance(updated_len);
+
+        if minimum_balance > mint_info.lamports() {
+            transfer(
+                CpiContext::new(self.system_program.to_account_info(), Transfer {
+                    from: self.creator.to_account_info(),
+                    to: mint_info.clone(),
+                }),
+                minimum_balance - mint_info.lamports()
+            )?;
+        }
+
+        for (field, value, _) in fields {
+            if let Some(value) = value {
+                invoke_signed(
+                    &update_field(
+                        &token_2022::ID,
+                        &mint_info.key(),
+                        &self.bounding_curve.key(),
+                        field,
+                        value.clone()
+                    ),
+                    &[
+                        mint_info.clone(),
+                        self.bounding_curve.to_account_info(),
+                        self.mint_token_program.to_account_info(),
+                    ],
+                    signer_seeds
+                )?;
+            }
+        }
+
+        Ok((
+            params.name.clone().unwrap_or(current.name),
+            params.symbol.clone().unwrap_or(current.symbol),
+            params.uri.clone().unwrap_or(current.uri),
+        ))
+    }
+}
@@ -64,6 +64,17 @@ pub mod pyth {
 pub const CONFIG_SEED: &str = "zeroboost";
 pub const CURVE_SEED: &str = "curve";
 pub const CURVE_RESERVE_SEED: &str = "curve_reserve";
+pub const CREATOR_FEE_VAULT_SEED: &str = "creator_fee_vault";
+pub const REFERRAL_SEED: &str = "referral";
+pub const NATIVE_PAIR_SEED: &str = "native_pair";
+pub const PAIR_ORACLE_SEED: &str = "pair_oracle";
+pub const TRADER_SEED: &str = "trader";
+pub const LIMIT_ORDER_SEED: &str = "limit_order";
+pub const BUYBACK_VAULT_SEED: &str = "buyback_vault";
+pub const PRESALE_CLAIM_SEED: &str = "presale_claim";
+pub const VESTING_SEED: &str = "vesting";
+
+pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
 
 #[program]
 pub mod zeroboost {
@@ -76,15 +87,72 @@ pub mod zeroboost {
         InitializeConfig::process_initialize(context, params)
     }
 
+    pub fn set_pair_oracle(context: Context<SetPairOracle>) -> Result<()> {
+        SetPairOracle::process_set_pair_oracle(context)
+    }
+
     pub fn mint_token(context: Context<MintToken>, params: MintTokenParams) -> Result<()> {
         MintToken::process_mint_token(context, &params)
     }
 
-    pub fn swap(context: Context<Swap>, params: SwapParams) -> Result<()> {
+    pub fn cancel_launch(context: Context<CancelLaunch>) -> Result<()> {
+        CancelLaunch::process_cancel_launch(context)
+    }
+
+    pub fn swap(context: Context<Swap>, params: SwapParams) -> Result<SwapResult> {
         Swap::process_swap(context, &params)
     }
 
+    pub fn quote_swap(context: Context<QuoteSwap>, params: SwapParams) -> Result<SwapResult> {
+        QuoteSwap::process_quote_swap(context, &params)
+    }
+
+    pub fn swap_route(context: Context<SwapRoute>, params: SwapRouteParams) -> Result<SwapResult> {
+        SwapRoute::process_swap_route(context, &params)
+    }
+
+    pub fn update_metadata(
+        context: Context<UpdateMetadata>,
+        params: UpdateMetadataParams,
+    ) -> Result<()> {
+        UpdateMetadata::process_update_metadata(context, &params)
+    }
+
     pub fn migrate_fund(context: Context<MigrateFund>, params: MigrateFundParams) -> Result<()> {
         MigrateFund::process_migrate_fund(context, &params)
     }
+
+    pub fn claim_creator_fees(context: Context<ClaimCreatorFees>) -> Result<()> {
+        ClaimCreatorFees::process_claim_creator_fees(context)
+    }
+
+    pub fn claim_vested(context: Context<ClaimVested>) -> Result<()> {
+        ClaimVested::process_claim_vested(context)
+    }
+
+    pub fn buyback_and_burn(
+        context: Context<BuybackAndBurn>,
+        params: BuybackAndBurnParams
+    ) -> Result<()> {
+        BuybackAndBurn::process_buyback_and_burn(context, &params)
+    }
+
+    pub fn register_referrer(context: Context<RegisterReferrer>) -> Result<()> {
+        RegisterReferrer::process_register_referrer(context)
+    }
+
+    pub fn place_limit_order(
+        context: Context<PlaceLimitOrder>,
+        params: PlaceLimitOrderParams,
+    ) -> Result<()> {
+        PlaceLimitOrder::process_place_limit_order(context, &params)
+    }
+
+    pub fn cancel_limit_order(context: Context<CancelLimitOrder>) -> Result<()> {
+        CancelLimitOrder::process_cancel_limit_order(context)
+    }
+
+    pub fn fill_limit_order(context: Context<FillLimitOrder>) -> Result<()> {
+        FillLimitOrder::process_fill_limit_order(context)
+    }
 }
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 pub const BOUNDING_CURVE_SIZE: usize =
-    8  + 1 + 1 + 1 + 8  + 8 + 8 + 8 + 8 + 8 + 32 + 32;
+    8  + 1 + 1 + 1 + 8  + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 2 + 8 + 32 + 8 + 8 + 8 + 8 + 1;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
 pub enum MigrationTarget {
@@ -21,6 +21,24 @@ pub struct BoundingCurve {
     pub maximum_pair_balance: u64, // 8
     pub virtual_token_balance: u64, // 8
     pub virtual_pair_balance: u64, // 8
+    pub creator: Pubkey, // 32
+    pub protection_ends_at: i64, // 8
+    pub protection_transaction_cap: u64, // 8
+    pub protection_wallet_cap: u64, // 8
+    pub same_slot_sell_restricted: bool, // 1
+    pub window_start_slot: u64, // 8
+    pub window_start_pair_balance: u64, // 8
+    pub paused_until_slot: u64, // 8
+    pub volatility_accumulator: u64, // 8
+    pub volatility_updated_at: i64, // 8
+    pub sell_tax_basis_points: u16, // 2
+    pub trading_starts_at: i64, // 8
+    pub presale_root: [u8; 32], // 32
+    pub presale_ends_at: i64, // 8
+    pub presale_wallet_cap: u64, // 8
+    pub presale_token_cap: u64, // 8
+    pub presale_sold: u64, // 8
+    pub traded: bool, // 1
 }
 
 impl BoundingCurve {
@@ -40,6 +58,34 @@ impl BoundingCurve {
         }
     }
 
+    // Exponentially decays the volatility accumulator by the elapsed number of half lives
+    pub fn decay_volatility(&mut self, now: i64, half_life: u32) {
+        let elapsed = now.saturating_sub(self.volatility_updated_at).max(0) as f64;
+
+        self.volatility_accumulator = match half_life {
+            0 => 0,
+            _ => ((self.volatility_accumulator as f64) *
+                (0.5_f64).powf(elapsed / (half_life as f64))) as u64,
+        };
+        self.volatility_updated_at = now;
+    }
+
+    // Set by the first buy or sell, balances alone can return to their launch values
+    pub fn is_untraded(&self) -> bool {
+        !self.migrated && !self.traded
+    }
+
+    // Allowlist phase runs until its end time or until the presale token cap is sold
+    pub fn is_presale_active(&self, now: i64) -> bool {
+        self.presale_root != [0; 32] &&
+            now < self.presale_ends_at &&
+            self.presale_sold < self.presale_token_cap
+    }
+
+    pub fn accumulate_volatility(&mut self, movement_basis_points: u64) {
+        self.volatility_accumulator = self.volatility_accumulator.saturating_add(movement_basis_points);
+    }
+
     pub fn copy(&self) -> Box<BoundingCurve> {
         Box::new(
           BoundingCurve {
@@ -50,10 +96,28 @@ impl BoundingCurve {
               liquidity_percentage: self.liquidity_percentage,
               initial_price: self.initial_price,
               initial_supply: self.initial_supply,
-              minimum_pair_balance: self.maximum_pair_balance,
+              minimum_pair_balance: self.minimum_pair_balance,
               maximum_pair_balance: self.maximum_pair_balance,
               virtual_token_balance: self.virtual_token_balance,
               virtual_pair_balance: self.virtual_pair_balance,
+              creator: self.creator,
+              protection_ends_at: self.protection_ends_at,
+              protection_transaction_cap: self.protection_transaction_cap,
+              protection_wallet_cap: self.protection_wallet_cap,
+              same_slot_sell_restricted: self.same_slot_sell_restricted,
+              window_start_slot: self.window_start_slot,
+              window_start_pair_balance: self.window_start_pair_balance,
+              paused_until_slot: self.paused_until_slot,
+              volatility_accumulator: self.volatility_accumulator,
+              volatility_updated_at: self.volatility_updated_at,
+              sell_tax_basis_points: self.sell_tax_basis_points,
+              trading_starts_at: self.trading_starts_at,
+              presale_root: self.presale_root,
+              presale_ends_at: self.presale_ends_at,
+              presale_wallet_cap: self.presale_wallet_cap,
+              presale_token_cap: self.presale_token_cap,
+              presale_sold: self.presale_sold,
+              traded: self.traded,
           }
         )
     }
@@ -8,6 +8,19 @@ pub struct Config {
     pub minimum_curve_usd_valuation: u16,
     pub maximum_curve_usd_valuation: u16,
     pub estimated_raydium_cp_pool_creation_fee: u64,
+    pub swap_fee_basis_points: u16,
+    pub creator_fee_share_basis_points: u16,
+    pub referral_fee_share_basis_points: u16,
+    pub maximum_protection_duration: u32,
+    pub minimum_protection_cap_basis_points: u16,
+    pub circuit_breaker_basis_points: u16,
+    pub circuit_breaker_window_slots: u64,
+    pub circuit_breaker_cooldown_slots: u64,
+    pub maximum_swap_fee_basis_points: u16,
+    pub volatility_reference_basis_points: u16,
+    pub volatility_half_life: u32,
+    pub limit_order_keeper_tip: u64,
+    pub maximum_sell_tax_basis_points: u16,
 }
 
-pub const CONFIG_SIZE: usize = 8 + 1 + 1 + 1 + 2 + 2 + 8;
+pub const CONFIG_SIZE: usize = 8 + 1 + 1 + 1 + 2 + 2 + 8 + 2 + 2 + 2 + 4 + 2 + 2 + 8 + 8 + 2 + 2 + 4 + 8 + 2;
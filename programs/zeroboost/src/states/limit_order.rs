@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+pub const LIMIT_ORDER_SIZE: usize = 8 + 32 + 32 + 8 + 1 + 8 + 8 + 8 + 1;
+
+#[account]
+pub struct LimitOrder {
+    pub owner: Pubkey, // 32
+    pub bounding_curve: Pubkey, // 32
+    pub id: u64, // 8
+    pub trade_direction: u8, // 1
+    pub amount_in: u64, // 8
+    pub minimum_amount_out: u64, // 8
+    pub keeper_tip: u64, // 8
+    pub bump: u8, // 1
+}
@@ -2,6 +2,12 @@ use anchor_lang::prelude::*;
 
 pub mod config;
 pub mod bounding_curve;
+pub mod referral;
+pub mod pair_oracle;
+pub mod trader;
+pub mod limit_order;
+pub mod presale_claim;
+pub mod vesting;
 
 
 #[account]
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+pub const PAIR_ORACLE_SIZE: usize = 8 + 32 + 32;
+
+#[account]
+pub struct PairOracle {
+    pub pair: Pubkey, // 32
+    pub feed: Pubkey, // 32
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+pub const PRESALE_CLAIM_SIZE: usize = 8 + 32 + 32 + 8;
+
+#[account]
+#[derive(Default)]
+pub struct PresaleClaim {
+    pub bounding_curve: Pubkey, // 32
+    pub wallet: Pubkey, // 32
+    pub amount: u64, // 8
+}
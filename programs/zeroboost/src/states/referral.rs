@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+pub const REFERRAL_SIZE: usize = 8 + 32 + 32 + 8 + 8;
+
+#[account]
+pub struct Referral {
+    pub referrer: Pubkey, // 32
+    pub pair: Pubkey, // 32
+    pub total_fees: u64, // 8
+    pub total_trades: u64, // 8
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+pub const TRADER_SIZE: usize = 8 + 32 + 32 + 8 + 8;
+
+#[account]
+#[derive(Default)]
+pub struct Trader {
+    pub bounding_curve: Pubkey, // 32
+    pub wallet: Pubkey, // 32
+    pub protected_buy_amount: u64, // 8
+    pub last_buy_slot: u64, // 8
+}
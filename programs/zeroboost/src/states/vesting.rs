@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+pub const VESTING_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+#[account]
+pub struct Vesting {
+    pub bounding_curve: Pubkey, // 32
+    pub beneficiary: Pubkey, // 32
+    pub total_amount: u64, // 8
+    pub claimed_amount: u64, // 8
+    pub start_at: i64, // 8
+    pub cliff_at: i64, // 8
+    pub end_at: i64, // 8
+    pub bump: u8, // 1
+}
+
+impl Vesting {
+    // Nothing unlocks before the cliff, after it the allocation unlocks linearly from the start
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_at {
+            return 0;
+        }
+
+        if now >= self.end_at {
+            return self.total_amount;
+        }
+
+        (((self.total_amount as u128) * ((now - self.start_at) as u128)) /
+            ((self.end_at - self.start_at) as u128)) as u64
+    }
+}
@@ -1,12 +1,184 @@
 use std::ops::Mul;
 
-use curve::safe_number::safe_number::{ SafeNumber, NewSafeNumber };
+use anchor_lang::{
+    err,
+    prelude::{ AccountInfo, Clock, CpiContext, Pubkey, SolanaSysvar },
+    solana_program::keccak::hashv,
+    AccountDeserialize,
+    Result,
+};
+use anchor_spl::{
+    token_2022::{
+        self,
+        spl_token_2022::{
+            extension::{
+                transfer_fee::TransferFeeConfig,
+                BaseStateWithExtensions,
+                ExtensionType,
+                StateWithExtensions,
+            },
+            instruction::AuthorityType,
+            state::Mint,
+        },
+    },
+    token_interface::{ set_authority, SetAuthority, TokenAccount },
+};
+use curve::safe_number::SafeNumber;
 use pyth_sdk_solana::Price;
+use raydium_cp_swap::states::POOL_SEED;
+
+use crate::{ error::SwapTokenError, BASIS_POINTS_DIVISOR };
+
+// Token 2022 extensions a launch mint may carry, anything else changes transfer semantics
+// in ways the curve and the raydium pool can't account for
+pub const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
 
 pub fn get_estimated_raydium_cp_pool_creation_fee() -> u64 {
     (2).mul((10_u64).pow(6)) + (15).mul((10_u64).pow(8)) + (203938).mul((10_u64).pow(1))
 }
 
 pub fn price_to_number(price: Price) -> SafeNumber {
-    f64::new((price.price as f64) / (10f64).powi(-price.expo as i32))
+    SafeNumber::from((price.price as f64) / (10f64).powi(-price.expo))
+}
+
+pub fn apply_basis_points(amount: u64, basis_points: u16) -> u64 {
+    ((amount as u128) * (basis_points as u128) / (BASIS_POINTS_DIVISOR as u128)) as u64
+}
+
+// Raydium cp pools expect token 0 to be the smaller mint
+pub fn sort_mints(a: Pubkey, b: Pubkey) -> (Pubkey, Pubkey) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+// Address of the raydium cp pool migrate_fund creates for a mint and its pair
+pub fn get_amm_pool_state_address(
+    amm_config: &Pubkey,
+    mint: Pubkey,
+    pair: Pubkey,
+    amm_program: &Pubkey
+) -> Pubkey {
+    let (token_0_mint, token_1_mint) = sort_mints(pair, mint);
+
+    Pubkey::find_program_address(
+        &[POOL_SEED.as_bytes(), amm_config.as_ref(), token_0_mint.as_ref(), token_1_mint.as_ref()],
+        amm_program
+    ).0
+}
+
+// Sorted pair keccak proof, leaves are the keccak hash of the allowlisted wallet
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let node = proof.iter().fold(leaf, |node, sibling| {
+        match node <= *sibling {
+            true => hashv(&[&node, sibling]).0,
+            false => hashv(&[sibling, &node]).0,
+        }
+    });
+
+    node == root
+}
+
+pub fn token_account_amount(account: &AccountInfo) -> Result<u64> {
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(token_account.amount)
+}
+
+pub fn validate_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    if
+        state
+            .get_extension_types()?
+            .iter()
+            .any(|extension| !SUPPORTED_MINT_EXTENSIONS.contains(extension))
+    {
+        return err!(SwapTokenError::UnsupportedMintExtension);
+    }
+
+    Ok(())
+}
+
+fn get_transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != token_2022::ID {
+        return Ok(None);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    Ok(state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+// Fee withheld from a transfer in the current epoch, zero for mints without a transfer fee
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match get_transfer_fee_config(mint)? {
+        Some(config) =>
+            config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or(SwapTokenError::InvalidAmount.into()),
+        None => Ok(0),
+    }
+}
+
+// Amount to send so the destination receives exactly amount after the transfer fee
+pub fn get_transfer_amount_with_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match get_transfer_fee_config(mint)? {
+        Some(config) => {
+            let fee = config
+                .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or(SwapTokenError::InvalidAmount)?;
+            Ok(amount + fee)
+        }
+        None => Ok(amount),
+    }
+}
+
+// Drops the mint and freeze authorities still held by the curve, returns which ones were revoked
+pub fn revoke_mint_authorities<'info>(
+    mint: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<(bool, bool)> {
+    let (has_mint_authority, has_freeze_authority) = {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<Mint>::unpack(&data)?;
+        (state.base.mint_authority.is_some(), state.base.freeze_authority.is_some())
+    };
+
+    let authority_types = [
+        (AuthorityType::MintTokens, has_mint_authority),
+        (AuthorityType::FreezeAccount, has_freeze_authority),
+    ];
+
+    for (authority_type, revoke) in authority_types {
+        if revoke {
+            set_authority(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    SetAuthority {
+                        current_authority: authority.clone(),
+                        account_or_mint: mint.clone(),
+                    },
+                    signer_seeds
+                ),
+                authority_type,
+                None
+            )?;
+        }
+    }
+
+    Ok((has_mint_authority, has_freeze_authority))
 }
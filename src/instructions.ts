@@ -1,5 +1,8 @@
-import { Program, web3 } from "@coral-xyz/anchor";
-import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { BN, Program, web3 } from "@coral-xyz/anchor";
+import {
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import {
   getCreatePoolKeys,
   getPdaAmmConfigId,
@@ -18,9 +21,25 @@ import {
   getBoundingCurveConfig,
   getBoundingCurvePda,
   getConfigPda,
+  getLimitOrderPda,
   getMintPda,
+  getPairOraclePda,
+  getPayerNativePairPda,
+  getPresaleClaimPda,
+  getReferralPda,
+  getTraderPda,
+  getVestingPda,
 } from "./pda";
 
+// Launch mints are owned by either the legacy token program or token 2022
+export const getMintTokenProgram = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey
+) => {
+  const { owner } = await program.provider.connection.getAccountInfo(mint);
+  return owner;
+};
+
 export const initializeConfig = (
   program: Program<Zeroboost>,
   admin: web3.PublicKey,
@@ -31,18 +50,36 @@ export const initializeConfig = (
   return program.methods.initializeConfig(params).accounts({ config, admin });
 };
 
+export const setPairOracle = (
+  program: Program<Zeroboost>,
+  pair: web3.PublicKey,
+  admin: web3.PublicKey,
+  pythPairUsdFeed: web3.PublicKey
+) => {
+  const [pairOracle] = getPairOraclePda(pair, program.programId);
+  return program.methods
+    .setPairOracle()
+    .accounts({ pair, pairOracle, pythPairUsdFeed, admin });
+};
+
+// Launches from a fresh mint keypair, or from the name, symbol and creator address without one
 export const mintToken = (
   program: Program<Zeroboost>,
+  mintSigner: web3.PublicKey | null,
   pair: web3.PublicKey,
   creator: web3.PublicKey,
   params: Parameters<(typeof program)["methods"]["mintToken"]>[number],
   pythPairUsdFeed: web3.PublicKey,
+  mintTokenProgram = TOKEN_PROGRAM_ID,
   tokenMetadataProgram = MPL_TOKEN_METADATA_PROGRAM_ID,
-  metadataFeeReciever = devnet.ZERO_BOOST_METADATA_FEE_RECIEVER
+  metadataFeeReciever = devnet.ZERO_BOOST_METADATA_FEE_RECIEVER,
+  migrationFeeReciever = devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
 ) => {
   const programId = program.programId;
+  const mint =
+    mintSigner ?? getMintPda(params.name, params.symbol, creator, programId)[0];
   const [config] = getConfigPda(programId);
-  const [mint] = getMintPda(params.name, params.symbol, creator, programId);
+  const [pairOracle] = getPairOraclePda(pair, programId);
   const [metadata] = findMetadataPda(createUmi(program.provider.connection), {
     mint: publicKey(mint),
   });
@@ -52,46 +89,254 @@ export const mintToken = (
     boundingCurveReserve,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
-  } = getBoundingCurveConfig(mint, pair, programId);
+    creatorFeeVault,
+    buybackVault,
+  } = getBoundingCurveConfig(mint, pair, programId, mintTokenProgram);
 
-  return program.methods.mintToken(params).accounts({
+  const creatorAta = getAssociatedTokenAddressSync(
+    mint,
+    creator,
+    false,
+    mintTokenProgram
+  );
+  const creatorPairAta = getAssociatedTokenAddressSync(pair, creator);
+  const protocolFeePairAta = getAssociatedTokenAddressSync(
+    pair,
+    migrationFeeReciever
+  );
+  const vesting = params.vesting
+    ? getVestingPda(boundingCurve, programId)[0]
+    : null;
+  const vestingAta = vesting
+    ? getAssociatedTokenAddressSync(mint, vesting, true, mintTokenProgram)
+    : null;
+  const trader =
+    params.initialBuyAmount &&
+    (params.launchProtection || params.restrictSameSlotSells)
+      ? getTraderPda(boundingCurve, creator, programId)[0]
+      : null;
+  const presaleClaim =
+    params.initialBuyAmount && params.presale
+      ? getPresaleClaimPda(boundingCurve, creator, programId)[0]
+      : null;
+
+  return program.methods
+    .mintToken(params)
+    .accounts({
+      mint,
+      pair,
+      config,
+      creator,
+      creatorAta,
+      creatorPairAta,
+      protocolFeePairAta,
+      metadata,
+      pairOracle,
+      pythPairUsdFeed,
+      boundingCurve,
+      boundingCurveAta,
+      boundingCurveReserve,
+      boundingCurveReserveAta,
+      boundingCurveReservePairAta,
+      creatorFeeVault,
+      buybackVault,
+      vesting,
+      vestingAta,
+      trader,
+      presaleClaim,
+      metadataFeeReciever,
+      mintTokenProgram,
+      tokenMetadataProgram,
+    })
+    // The program account only declares the mint writable, a keypair mint also signs the launch
+    .remainingAccounts(
+      mintSigner
+        ? [{ pubkey: mintSigner, isSigner: true, isWritable: true }]
+        : []
+    );
+};
+
+export const cancelLaunch = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  creator: web3.PublicKey
+) => {
+  const programId = program.programId;
+  const [boundingCurve] = getBoundingCurvePda(mint, programId);
+  const { pair } = await program.account.boundingCurve.fetch(boundingCurve);
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
+  const {
+    boundingCurveAta,
+    boundingCurveReserve,
+    boundingCurveReserveAta,
+    boundingCurveReservePairAta,
+    creatorFeeVault,
+    buybackVault,
+  } = getBoundingCurveConfig(mint, pair, programId, mintTokenProgram);
+  // Vesting is always passed, its escrow only when the launch created one
+  const [vesting] = getVestingPda(boundingCurve, programId);
+  const vestingAta = (await program.account.vesting.fetchNullable(vesting))
+    ? getAssociatedTokenAddressSync(mint, vesting, true, mintTokenProgram)
+    : null;
+  const creatorPairAta = getAssociatedTokenAddressSync(pair, creator);
+
+  return program.methods.cancelLaunch().accounts({
     mint,
     pair,
-    config,
     creator,
-    metadata,
-    pythPairUsdFeed,
+    creatorPairAta,
     boundingCurve,
     boundingCurveAta,
     boundingCurveReserve,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
-    metadataFeeReciever,
+    creatorFeeVault,
+    buybackVault,
+    vesting,
+    vestingAta,
+    mintTokenProgram,
+  });
+};
+
+// Metaplex metadata only exists for legacy token mints
+const getLegacyMetadataAccounts = (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  mintTokenProgram: web3.PublicKey
+) => {
+  if (!mintTokenProgram.equals(TOKEN_PROGRAM_ID))
+    return { metadata: null, tokenMetadataProgram: null };
+
+  const [metadata] = findMetadataPda(createUmi(program.provider.connection), {
+    mint: publicKey(mint),
+  });
+
+  return {
+    metadata,
+    tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID,
+  };
+};
+
+export const updateMetadata = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  creator: web3.PublicKey,
+  params: Parameters<(typeof program)["methods"]["updateMetadata"]>[number]
+) => {
+  const [boundingCurve] = getBoundingCurvePda(mint, program.programId);
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
+  const { metadata, tokenMetadataProgram } = getLegacyMetadataAccounts(
+    program,
+    mint,
+    mintTokenProgram
+  );
+
+  return program.methods.updateMetadata(params).accounts({
+    mint,
+    creator,
+    boundingCurve,
+    metadata,
     tokenMetadataProgram,
+    mintTokenProgram,
   });
 };
 
-export const swap = async (
+const getAmmSwapAccounts = (
+  mint: web3.PublicKey,
+  pair: web3.PublicKey,
+  raydiumCpPoolProgram = devnet.RAYDIUM_CP_POOL_PROGRAM
+) => {
+  const { publicKey: configId } = getPdaAmmConfigId(raydiumCpPoolProgram, 0);
+  const pairIsMintA = pair.toBuffer().compare(mint.toBuffer()) < 0;
+  const poolkeys = getCreatePoolKeys({
+    configId,
+    mintA: pairIsMintA ? pair : mint,
+    mintB: pairIsMintA ? mint : pair,
+    programId: raydiumCpPoolProgram,
+  });
+
+  return {
+    ammConfig: poolkeys.configId,
+    ammAuthority: poolkeys.authority,
+    ammPoolState: poolkeys.poolId,
+    ammMintVault: pairIsMintA ? poolkeys.vaultB : poolkeys.vaultA,
+    ammPairVault: pairIsMintA ? poolkeys.vaultA : poolkeys.vaultB,
+    ammObservableState: poolkeys.observationId,
+    ammProgram: raydiumCpPoolProgram,
+  };
+};
+
+const noAmmSwapAccounts = {
+  ammConfig: null,
+  ammAuthority: null,
+  ammPoolState: null,
+  ammMintVault: null,
+  ammPairVault: null,
+  ammObservableState: null,
+  ammProgram: null,
+};
+
+// Swap and quote swap take the same accounts, so a quote runs against the keys the swap will use
+const getSwapAccounts = async (
   program: Program<Zeroboost>,
   mint: web3.PublicKey,
   payer: web3.PublicKey,
   params: Parameters<(typeof program)["methods"]["swap"]>[number],
+  referrer?: web3.PublicKey,
+  raydiumCpPoolProgram = devnet.RAYDIUM_CP_POOL_PROGRAM,
+  migrationFeeReciever = devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
 ) => {
   const programId = program.programId;
 
   const [config] = getConfigPda(programId);
   const [boundingCurve] = getBoundingCurvePda(mint, programId);
-  const { pair } = await program.account.boundingCurve.fetch(boundingCurve);
+  const {
+    pair,
+    migrated,
+    protectionEndsAt,
+    sameSlotSellRestricted,
+    presaleEndsAt,
+  } = await program.account.boundingCurve.fetch(boundingCurve);
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
   const {
     boundingCurveReserve,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
-  } = getBoundingCurveConfig(mint, pair, programId);
+    creatorFeeVault,
+    buybackVault,
+  } = getBoundingCurveConfig(mint, pair, programId, mintTokenProgram);
+  const trader =
+    sameSlotSellRestricted || protectionEndsAt.gtn(Date.now() / 1000)
+      ? getTraderPda(boundingCurve, payer, programId)[0]
+      : null;
+  const presaleClaim =
+    params.presaleProof && presaleEndsAt.gtn(Date.now() / 1000)
+      ? getPresaleClaimPda(boundingCurve, payer, programId)[0]
+      : null;
+  const ammAccounts = migrated
+    ? getAmmSwapAccounts(mint, pair, raydiumCpPoolProgram)
+    : noAmmSwapAccounts;
 
-  const payerAta = getAssociatedTokenAddressSync(mint, payer);
+  const payerAta = getAssociatedTokenAddressSync(
+    mint,
+    payer,
+    false,
+    mintTokenProgram
+  );
   const payerPairAta = getAssociatedTokenAddressSync(pair, payer);
+  const protocolFeePairAta = getAssociatedTokenAddressSync(
+    pair,
+    migrationFeeReciever
+  );
+  const referral = referrer ? getReferralPda(referrer, pair, programId)[0] : null;
+  const referrerPairAta = referrer
+    ? getAssociatedTokenAddressSync(pair, referrer)
+    : null;
+  const payerNativePair = params.native
+    ? getPayerNativePairPda(payer, programId)[0]
+    : null;
 
-  return program.methods.swap(params).accounts({
+  return {
     mint,
     pair,
     payer,
@@ -102,7 +347,58 @@ export const swap = async (
     boundingCurveReserve,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
-  });
+    creatorFeeVault,
+    protocolFeePairAta,
+    buybackVault,
+    referral,
+    referrerPairAta,
+    payerNativePair,
+    trader,
+    presaleClaim,
+    mintTokenProgram,
+    ...ammAccounts,
+  };
+};
+
+export const swap = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  payer: web3.PublicKey,
+  params: Parameters<(typeof program)["methods"]["swap"]>[number],
+  referrer?: web3.PublicKey,
+  raydiumCpPoolProgram = devnet.RAYDIUM_CP_POOL_PROGRAM,
+) => {
+  const accounts = await getSwapAccounts(
+    program,
+    mint,
+    payer,
+    params,
+    referrer,
+    raydiumCpPoolProgram
+  );
+
+  return program.methods.swap(params).accounts(accounts);
+};
+
+// Simulated through `.view()`, returns the same result swap would for the current curve state
+export const quoteSwap = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  payer: web3.PublicKey,
+  params: Parameters<(typeof program)["methods"]["quoteSwap"]>[number],
+  referrer?: web3.PublicKey,
+  raydiumCpPoolProgram = devnet.RAYDIUM_CP_POOL_PROGRAM,
+) => {
+  const accounts = await getSwapAccounts(
+    program,
+    mint,
+    payer,
+    params,
+    referrer,
+    raydiumCpPoolProgram
+  );
+
+  return program.methods.quoteSwap(params).accounts(accounts);
 };
 
 export const rawSwap = async (
@@ -111,6 +407,9 @@ export const rawSwap = async (
   pair: web3.PublicKey,
   payer: web3.PublicKey,
   params: Parameters<(typeof program)["methods"]["swap"]>[number],
+  referrer?: web3.PublicKey,
+  mintTokenProgram = TOKEN_PROGRAM_ID,
+  migrationFeeReciever = devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
 ) => {
   const programId = program.programId;
   const [config] = getConfigPda(programId);
@@ -120,10 +419,28 @@ export const rawSwap = async (
     boundingCurveReserve,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
-  } = getBoundingCurveConfig(mint, pair, programId);
+    creatorFeeVault,
+    buybackVault,
+  } = getBoundingCurveConfig(mint, pair, programId, mintTokenProgram);
 
-  const payerAta = getAssociatedTokenAddressSync(mint, payer);
+  const payerAta = getAssociatedTokenAddressSync(
+    mint,
+    payer,
+    false,
+    mintTokenProgram
+  );
   const payerPairAta = getAssociatedTokenAddressSync(pair, payer);
+  const protocolFeePairAta = getAssociatedTokenAddressSync(
+    pair,
+    migrationFeeReciever
+  );
+  const referral = referrer ? getReferralPda(referrer, pair, programId)[0] : null;
+  const referrerPairAta = referrer
+    ? getAssociatedTokenAddressSync(pair, referrer)
+    : null;
+  const payerNativePair = params.native
+    ? getPayerNativePairPda(payer, programId)[0]
+    : null;
 
   return program.methods.swap(params).accounts({
     mint,
@@ -136,6 +453,325 @@ export const rawSwap = async (
     boundingCurveReserve,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
+    creatorFeeVault,
+    protocolFeePairAta,
+    buybackVault,
+    referral,
+    referrerPairAta,
+    payerNativePair,
+    trader: null,
+    presaleClaim: null,
+    mintTokenProgram,
+    ...noAmmSwapAccounts,
+  });
+};
+
+const getRouteLegConfig = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  payer: web3.PublicKey
+) => {
+  const programId = program.programId;
+  const [boundingCurve] = getBoundingCurvePda(mint, programId);
+  const { pair, protectionEndsAt, sameSlotSellRestricted } =
+    await program.account.boundingCurve.fetch(boundingCurve);
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
+  const {
+    boundingCurveReserve,
+    boundingCurveReserveAta,
+    boundingCurveReservePairAta,
+    creatorFeeVault,
+    buybackVault,
+  } = getBoundingCurveConfig(mint, pair, programId, mintTokenProgram);
+  const trader =
+    sameSlotSellRestricted || protectionEndsAt.gtn(Date.now() / 1000)
+      ? getTraderPda(boundingCurve, payer, programId)[0]
+      : null;
+
+  return {
+    pair,
+    boundingCurve,
+    boundingCurveReserve,
+    boundingCurveReserveAta,
+    boundingCurveReservePairAta,
+    creatorFeeVault,
+    buybackVault,
+    trader,
+    mintTokenProgram,
+    payerAta: getAssociatedTokenAddressSync(
+      mint,
+      payer,
+      false,
+      mintTokenProgram
+    ),
+  };
+};
+
+export const swapRoute = async (
+  program: Program<Zeroboost>,
+  sourceMint: web3.PublicKey,
+  destinationMint: web3.PublicKey,
+  payer: web3.PublicKey,
+  params: Parameters<(typeof program)["methods"]["swapRoute"]>[number],
+  migrationFeeReciever = devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
+) => {
+  const programId = program.programId;
+  const [config] = getConfigPda(programId);
+  const source = await getRouteLegConfig(program, sourceMint, payer);
+  const destination = await getRouteLegConfig(program, destinationMint, payer);
+
+  return program.methods.swapRoute(params).accounts({
+    sourceMint,
+    destinationMint,
+    pair: source.pair,
+    config,
+    payer,
+    sourceBoundingCurve: source.boundingCurve,
+    sourceBoundingCurveReserve: source.boundingCurveReserve,
+    sourceBoundingCurveReserveAta: source.boundingCurveReserveAta,
+    sourceBoundingCurveReservePairAta: source.boundingCurveReservePairAta,
+    sourceCreatorFeeVault: source.creatorFeeVault,
+    sourceBuybackVault: source.buybackVault,
+    sourceTrader: source.trader,
+    destinationBoundingCurve: destination.boundingCurve,
+    destinationBoundingCurveReserve: destination.boundingCurveReserve,
+    destinationBoundingCurveReserveAta: destination.boundingCurveReserveAta,
+    destinationBoundingCurveReservePairAta:
+      destination.boundingCurveReservePairAta,
+    destinationCreatorFeeVault: destination.creatorFeeVault,
+    destinationBuybackVault: destination.buybackVault,
+    destinationTrader: destination.trader,
+    payerSourceAta: source.payerAta,
+    payerDestinationAta: destination.payerAta,
+    payerPairAta: getAssociatedTokenAddressSync(source.pair, payer),
+    protocolFeePairAta: getAssociatedTokenAddressSync(
+      source.pair,
+      migrationFeeReciever
+    ),
+    sourceMintTokenProgram: source.mintTokenProgram,
+    destinationMintTokenProgram: destination.mintTokenProgram,
+    zeroboostProgram: programId,
+  });
+};
+
+export const buybackAndBurn = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  authority: web3.PublicKey,
+  params: Parameters<(typeof program)["methods"]["buybackAndBurn"]>[number],
+  raydiumCpPoolProgram = devnet.RAYDIUM_CP_POOL_PROGRAM
+) => {
+  const programId = program.programId;
+  const [config] = getConfigPda(programId);
+  const [boundingCurve] = getBoundingCurvePda(mint, programId);
+  const { pair, migrated } = await program.account.boundingCurve.fetch(
+    boundingCurve
+  );
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
+  const {
+    boundingCurveReserve,
+    boundingCurveReserveAta,
+    boundingCurveReservePairAta,
+    buybackVault,
+  } = getBoundingCurveConfig(mint, pair, programId, mintTokenProgram);
+  const ammAccounts = migrated
+    ? getAmmSwapAccounts(mint, pair, raydiumCpPoolProgram)
+    : noAmmSwapAccounts;
+
+  return program.methods.buybackAndBurn(params).accounts({
+    mint,
+    pair,
+    boundingCurve,
+    config,
+    boundingCurveReserve,
+    boundingCurveReserveAta,
+    boundingCurveReservePairAta,
+    buybackVault,
+    authority,
+    mintTokenProgram,
+    ...ammAccounts,
+  });
+};
+
+export const claimVested = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  beneficiary: web3.PublicKey
+) => {
+  const programId = program.programId;
+  const [boundingCurve] = getBoundingCurvePda(mint, programId);
+  const [vesting] = getVestingPda(boundingCurve, programId);
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
+
+  return program.methods.claimVested().accounts({
+    mint,
+    boundingCurve,
+    vesting,
+    vestingAta: getAssociatedTokenAddressSync(
+      mint,
+      vesting,
+      true,
+      mintTokenProgram
+    ),
+    beneficiary,
+    beneficiaryAta: getAssociatedTokenAddressSync(
+      mint,
+      beneficiary,
+      false,
+      mintTokenProgram
+    ),
+    mintTokenProgram,
+  });
+};
+
+export const registerReferrer = (
+  program: Program<Zeroboost>,
+  pair: web3.PublicKey,
+  referrer: web3.PublicKey
+) => {
+  const [referral] = getReferralPda(referrer, pair, program.programId);
+  const referrerPairAta = getAssociatedTokenAddressSync(pair, referrer);
+
+  return program.methods.registerReferrer().accounts({
+    pair,
+    referral,
+    referrer,
+    referrerPairAta,
+  });
+};
+
+const getLimitOrderConfig = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+  id: BN
+) => {
+  const programId = program.programId;
+  const [boundingCurve] = getBoundingCurvePda(mint, programId);
+  const { pair } = await program.account.boundingCurve.fetch(boundingCurve);
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
+  const [limitOrder] = getLimitOrderPda(boundingCurve, owner, id, programId);
+  const [limitOrderTrader] = getTraderPda(boundingCurve, limitOrder, programId);
+  const [ownerTrader] = getTraderPda(boundingCurve, owner, programId);
+
+  return {
+    pair,
+    boundingCurve,
+    limitOrder,
+    limitOrderTrader,
+    ownerTrader,
+    mintTokenProgram,
+    limitOrderAta: getAssociatedTokenAddressSync(
+      mint,
+      limitOrder,
+      true,
+      mintTokenProgram
+    ),
+    limitOrderPairAta: getAssociatedTokenAddressSync(pair, limitOrder, true),
+    ownerAta: getAssociatedTokenAddressSync(
+      mint,
+      owner,
+      false,
+      mintTokenProgram
+    ),
+    ownerPairAta: getAssociatedTokenAddressSync(pair, owner),
+  };
+};
+
+export const placeLimitOrder = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+  params: Parameters<(typeof program)["methods"]["placeLimitOrder"]>[number]
+) => {
+  const [config] = getConfigPda(program.programId);
+  const accounts = await getLimitOrderConfig(program, mint, owner, params.id);
+
+  return program.methods
+    .placeLimitOrder(params)
+    .accounts({ mint, owner, config, ...accounts });
+};
+
+export const cancelLimitOrder = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+  id: BN
+) => {
+  const accounts = await getLimitOrderConfig(program, mint, owner, id);
+
+  return program.methods
+    .cancelLimitOrder()
+    .accounts({ mint, owner, ...accounts });
+};
+
+export const fillLimitOrder = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+  id: BN,
+  keeper: web3.PublicKey,
+  migrationFeeReciever = devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
+) => {
+  const programId = program.programId;
+  const [config] = getConfigPda(programId);
+  const accounts = await getLimitOrderConfig(program, mint, owner, id);
+  const {
+    boundingCurveReserve,
+    boundingCurveReserveAta,
+    boundingCurveReservePairAta,
+    creatorFeeVault,
+    buybackVault,
+  } = getBoundingCurveConfig(
+    mint,
+    accounts.pair,
+    programId,
+    accounts.mintTokenProgram
+  );
+  const protocolFeePairAta = getAssociatedTokenAddressSync(
+    accounts.pair,
+    migrationFeeReciever
+  );
+
+  return program.methods.fillLimitOrder().accounts({
+    mint,
+    owner,
+    keeper,
+    config,
+    boundingCurveReserve,
+    boundingCurveReserveAta,
+    boundingCurveReservePairAta,
+    creatorFeeVault,
+    protocolFeePairAta,
+    buybackVault,
+    zeroboostProgram: programId,
+    ...accounts,
+  });
+};
+
+export const claimCreatorFees = async (
+  program: Program<Zeroboost>,
+  mint: web3.PublicKey,
+  creator: web3.PublicKey
+) => {
+  const programId = program.programId;
+  const [boundingCurve] = getBoundingCurvePda(mint, programId);
+  const { pair } = await program.account.boundingCurve.fetch(boundingCurve);
+  const { boundingCurveReserve, creatorFeeVault } = getBoundingCurveConfig(
+    mint,
+    pair,
+    programId
+  );
+  const creatorPairAta = getAssociatedTokenAddressSync(pair, creator);
+
+  return program.methods.claimCreatorFees().accounts({
+    mint,
+    pair,
+    creator,
+    creatorPairAta,
+    creatorFeeVault,
+    boundingCurve,
+    boundingCurveReserve,
   });
 };
 
@@ -153,18 +789,25 @@ export const migrateFund = async (
   const { mint, pair } = await program.account.boundingCurve.fetch(
     boundingCurve
   );
+  const mintTokenProgram = await getMintTokenProgram(program, mint);
+  const { metadata, tokenMetadataProgram } = getLegacyMetadataAccounts(
+    program,
+    mint,
+    mintTokenProgram
+  );
   const payerPairAta = getAssociatedTokenAddressSync(pair, payer);
   const {
     boundingCurveAta,
     boundingCurveReserve,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
-  } = getBoundingCurveConfig(mint, pair, programId);
+  } = getBoundingCurveConfig(mint, pair, programId, mintTokenProgram);
   const { publicKey: configId } = getPdaAmmConfigId(raydiumCpPoolProgram, 0);
+  const pairIsMintA = pair.toBuffer().compare(mint.toBuffer()) < 0;
   const poolkeys = getCreatePoolKeys({
     configId,
-    mintA: pair,
-    mintB: mint,
+    mintA: pairIsMintA ? pair : mint,
+    mintB: pairIsMintA ? mint : pair,
     programId: raydiumCpPoolProgram,
   });
 
@@ -189,11 +832,14 @@ export const migrateFund = async (
     ammConfig: poolkeys.configId,
     ammAuthority: poolkeys.authority,
     ammLpMint: poolkeys.lpMint,
-    ammMintVault: poolkeys.vaultB,
-    ammPairVault: poolkeys.vaultA,
+    ammMintVault: pairIsMintA ? poolkeys.vaultB : poolkeys.vaultA,
+    ammPairVault: pairIsMintA ? poolkeys.vaultA : poolkeys.vaultB,
     ammProgram: raydiumCpPoolProgram,
     ammFeeReceiver: raydiumCpPoolFeeReciever,
     ammPoolState: poolkeys.poolId,
     ammObservableState: poolkeys.observationId,
+    metadata,
+    tokenMetadataProgram,
+    mintTokenProgram,
   });
 };
@@ -1,5 +1,8 @@
-import { web3 } from "@coral-xyz/anchor";
-import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { BN, web3 } from "@coral-xyz/anchor";
+import {
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import { ZERO_BOOST_PROGRAM } from ".";
 
 export const getConfigPda = (programId = ZERO_BOOST_PROGRAM) =>
@@ -12,7 +15,18 @@ export const getMintPda = (
   programId = ZERO_BOOST_PROGRAM
 ) => {
   const seeds = [name, symbol].map(Buffer.from);
-  return web3.PublicKey.findProgramAddressSync([...seeds, creator.toBytes()], programId);
+  return web3.PublicKey.findProgramAddressSync(
+    [...seeds, creator.toBytes()],
+    programId
+  );
+};
+
+export const getPairOraclePda = (
+  pair: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [pair.toBuffer(), Buffer.from("pair_oracle")];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
 };
 
 export const getBoundingCurvePda = (
@@ -31,25 +45,108 @@ export const getBoundingCurveReservePda = (
   return web3.PublicKey.findProgramAddressSync(seeds, programId);
 };
 
+export const getCreatorFeeVaultPda = (
+  boundingCurve: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [boundingCurve.toBuffer(), Buffer.from("creator_fee_vault")];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
+export const getBuybackVaultPda = (
+  boundingCurve: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [boundingCurve.toBuffer(), Buffer.from("buyback_vault")];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
+export const getReferralPda = (
+  referrer: web3.PublicKey,
+  pair: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [referrer.toBuffer(), pair.toBuffer(), Buffer.from("referral")];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
+export const getPayerNativePairPda = (
+  payer: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [payer.toBuffer(), Buffer.from("native_pair")];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
+export const getTraderPda = (
+  boundingCurve: web3.PublicKey,
+  wallet: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [boundingCurve.toBuffer(), wallet.toBuffer(), Buffer.from("trader")];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
+export const getPresaleClaimPda = (
+  boundingCurve: web3.PublicKey,
+  wallet: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [
+    boundingCurve.toBuffer(),
+    wallet.toBuffer(),
+    Buffer.from("presale_claim"),
+  ];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
+export const getVestingPda = (
+  boundingCurve: web3.PublicKey,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [boundingCurve.toBuffer(), Buffer.from("vesting")];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
+export const getLimitOrderPda = (
+  boundingCurve: web3.PublicKey,
+  owner: web3.PublicKey,
+  id: BN,
+  programId = ZERO_BOOST_PROGRAM
+) => {
+  const seeds = [
+    boundingCurve.toBuffer(),
+    owner.toBuffer(),
+    id.toArrayLike(Buffer, "le", 8),
+    Buffer.from("limit_order"),
+  ];
+  return web3.PublicKey.findProgramAddressSync(seeds, programId);
+};
+
 export const getBoundingCurveConfig = (
   mint: web3.PublicKey,
   pair: web3.PublicKey,
-  programId = ZERO_BOOST_PROGRAM
+  programId = ZERO_BOOST_PROGRAM,
+  mintTokenProgram = TOKEN_PROGRAM_ID
 ) => {
   const [boundingCurve] = getBoundingCurvePda(mint, programId);
   const [boundingCurveReserve] = getBoundingCurveReservePda(
     boundingCurve,
     programId
   );
+  const [creatorFeeVault] = getCreatorFeeVaultPda(boundingCurve, programId);
+  const [buybackVault] = getBuybackVaultPda(boundingCurve, programId);
   const boundingCurveAta = getAssociatedTokenAddressSync(
     mint,
     boundingCurve,
-    true
+    true,
+    mintTokenProgram
   );
   const boundingCurveReserveAta = getAssociatedTokenAddressSync(
     mint,
     boundingCurveReserve,
-    true
+    true,
+    mintTokenProgram
   );
   const boundingCurveReservePairAta = getAssociatedTokenAddressSync(
     pair,
@@ -63,6 +160,8 @@ export const getBoundingCurveConfig = (
     boundingCurveAta,
     boundingCurveReserveAta,
     boundingCurveReservePairAta,
+    creatorFeeVault,
+    buybackVault,
   };
 };
 
@@ -1,4 +1,5 @@
-import { BN } from "@coral-xyz/anchor";
+import { BN, web3 } from "@coral-xyz/anchor";
+import { keccak_256 } from "@noble/hashes/sha3";
 
 export function getEstimatedRaydiumCpPoolCreationFee() {
   return new BN(2)
@@ -6,3 +7,51 @@ export function getEstimatedRaydiumCpPoolCreationFee() {
     .add(new BN(15).mul(new BN(10).pow(new BN(8))))
     .add(new BN(203938).mul(new BN(10).pow(new BN(1))));
 }
+
+const hashSortedPair = (left: Buffer, right: Buffer) =>
+  Buffer.from(
+    keccak_256(
+      Buffer.compare(left, right) <= 0
+        ? Buffer.concat([left, right])
+        : Buffer.concat([right, left])
+    )
+  );
+
+// Keccak tree over sorted pairs of wallet leaves, matching the on chain presale proof check
+export function getPresaleTree(wallets: web3.PublicKey[]) {
+  const leaves = wallets.map((wallet) =>
+    Buffer.from(keccak_256(wallet.toBuffer()))
+  );
+  const layers = [leaves];
+
+  while (layers[layers.length - 1].length > 1) {
+    const layer = layers[layers.length - 1];
+    const nextLayer: Buffer[] = [];
+
+    for (let index = 0; index < layer.length; index += 2) {
+      nextLayer.push(
+        index + 1 < layer.length
+          ? hashSortedPair(layer[index], layer[index + 1])
+          : layer[index]
+      );
+    }
+
+    layers.push(nextLayer);
+  }
+
+  const getProof = (wallet: web3.PublicKey) => {
+    const leaf = Buffer.from(keccak_256(wallet.toBuffer()));
+    const proof: number[][] = [];
+    let index = leaves.findIndex((value) => value.equals(leaf));
+
+    for (const layer of layers.slice(0, -1)) {
+      const sibling = index ^ 1;
+      if (sibling < layer.length) proof.push(Array.from(layer[sibling]));
+      index >>= 1;
+    }
+
+    return proof;
+  };
+
+  return { root: Array.from(layers[layers.length - 1][0]), getProof };
+}
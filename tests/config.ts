@@ -6,6 +6,19 @@ type ConfigParams = {
   migrationPercentageFee: number;
   minimumCurveUsdValuation: number;
   maximumCurveUsdValuation: number;
+  swapFeeBasisPoints: number;
+  creatorFeeShareBasisPoints: number;
+  referralFeeShareBasisPoints: number;
+  maximumProtectionDuration: number;
+  minimumProtectionCapBasisPoints: number;
+  circuitBreakerBasisPoints: number;
+  circuitBreakerWindowSlots: BN;
+  circuitBreakerCooldownSlots: BN;
+  maximumSwapFeeBasisPoints: number;
+  volatilityReferenceBasisPoints: number;
+  volatilityHalfLife: number;
+  limitOrderKeeperTip: BN;
+  maximumSellTaxBasisPoints: number;
   liquidityPercentage: number;
   mint: {
     name: string;
@@ -27,13 +40,14 @@ export const buildConfig = (
   const SOL_USD_FEED = new web3.PublicKey(
     "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix"
   );
+  const USDC_USD_FEED = new web3.PublicKey(
+    "5SSkXsEKQepHHAewytPVwdej4epN1nxgLVM84L4KXgy7"
+  );
 
   const supply = BigInt(mintParams.supply) * BigInt(Math.pow(10, 9));
 
-  const [mint] = web3.PublicKey.findProgramAddressSync(
-    [Buffer.from(name), Buffer.from(symbol), program.provider.publicKey!.toBytes()],
-    program.programId
-  );
+  const mintKeypair = web3.Keypair.generate();
+  const mint = mintKeypair.publicKey;
 
   const [boundingCurve] = web3.PublicKey.findProgramAddressSync(
     [mint.toBuffer(), Buffer.from("curve")],
@@ -47,8 +61,10 @@ export const buildConfig = (
     supply,
     decimals,
     mint,
+    mintKeypair,
     boundingCurve,
     SOL_USD_FEED,
+    USDC_USD_FEED,
     liquidityPercentage,
     ...params,
   };
@@ -1,17 +1,66 @@
 import { expect } from "chai";
 
-import { Program, web3 } from "@coral-xyz/anchor";
+import {
+  AnchorError,
+  BorshCoder,
+  EventParser,
+  Program,
+  web3,
+} from "@coral-xyz/anchor";
 import { workspace, setProvider, AnchorProvider, BN } from "@coral-xyz/anchor";
 
-import { MintLayout, NATIVE_MINT } from "@solana/spl-token";
+import {
+  createAssociatedTokenAccountIdempotentInstruction,
+  createInitializeMint2Instruction,
+  createMintToInstruction,
+  createSyncNativeInstruction,
+  createTransferCheckedInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getMinimumBalanceForRentExemptMint,
+  getTokenMetadata,
+  MINT_SIZE,
+  MintLayout,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import { Amman } from "@metaplex-foundation/amman-client";
+import {
+  fetchMetadata,
+  findMetadataPda,
+} from "@metaplex-foundation/mpl-token-metadata";
+import { publicKey } from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
 
 import {
+  buybackAndBurn,
+  cancelLaunch,
+  cancelLimitOrder,
+  claimCreatorFees,
+  claimVested,
+  devnet,
+  fillLimitOrder,
+  getBoundingCurveConfig,
   getEstimatedRaydiumCpPoolCreationFee,
+  getLimitOrderPda,
+  getMintPda,
+  getPayerNativePairPda,
+  getPresaleTree,
+  getReferralPda,
+  getTraderPda,
+  getVestingPda,
   initializeConfig,
   migrateFund,
   mintToken,
+  placeLimitOrder,
+  quoteSwap,
+  registerReferrer,
+  setPairOracle,
   swap,
+  swapRoute,
+  updateMetadata,
 } from "../src";
 import { Zeroboost } from "../target/types/zeroboost";
 import { buildConfig } from "./config";
@@ -28,13 +77,28 @@ describe("zeroboost", async () => {
     migrationPercentageFee,
     minimumCurveUsdValuation,
     maximumCurveUsdValuation,
+    swapFeeBasisPoints,
+    creatorFeeShareBasisPoints,
+    referralFeeShareBasisPoints,
+    maximumProtectionDuration,
+    minimumProtectionCapBasisPoints,
+    circuitBreakerBasisPoints,
+    circuitBreakerWindowSlots,
+    circuitBreakerCooldownSlots,
+    maximumSwapFeeBasisPoints,
+    volatilityReferenceBasisPoints,
+    volatilityHalfLife,
+    limitOrderKeeperTip,
+    maximumSellTaxBasisPoints,
     liquidityPercentage,
     name,
     supply,
     symbol,
     uri,
     SOL_USD_FEED,
+    USDC_USD_FEED,
     mint,
+    mintKeypair,
     boundingCurve,
     decimals,
   } = buildConfig(program, {
@@ -42,6 +106,19 @@ describe("zeroboost", async () => {
     migrationPercentageFee: 5,
     minimumCurveUsdValuation: 4000,
     maximumCurveUsdValuation: 60000,
+    swapFeeBasisPoints: 100,
+    creatorFeeShareBasisPoints: 5000,
+    referralFeeShareBasisPoints: 1000,
+    maximumProtectionDuration: 300,
+    minimumProtectionCapBasisPoints: 10,
+    circuitBreakerBasisPoints: 0,
+    circuitBreakerWindowSlots: new BN(10),
+    circuitBreakerCooldownSlots: new BN(150),
+    maximumSwapFeeBasisPoints: 300,
+    volatilityReferenceBasisPoints: 5000,
+    volatilityHalfLife: 60,
+    limitOrderKeeperTip: new BN(100_000),
+    maximumSellTaxBasisPoints: 1_000,
     liquidityPercentage: 25,
     mint: {
       name: "FliedLice",
@@ -52,17 +129,46 @@ describe("zeroboost", async () => {
     },
   });
 
+  const expectError = async (transaction: Promise<unknown>, code: string) => {
+    let error: any;
+    try {
+      await transaction;
+    } catch (caught) {
+      error = caught;
+    }
+    // Raw transactions are not translated by anchor, their error is parsed from the logs
+    const errorCode =
+      error?.error?.errorCode?.code ??
+      AnchorError.parse(error?.logs)?.error.errorCode.code;
+    expect(errorCode).equal(code, `Expected ${code}`);
+  };
+
+  const configParams = {
+    metadataCreationFee,
+    migrationPercentageFee,
+    minimumCurveUsdValuation,
+    maximumCurveUsdValuation,
+    swapFeeBasisPoints,
+    creatorFeeShareBasisPoints,
+    referralFeeShareBasisPoints,
+    maximumProtectionDuration,
+    minimumProtectionCapBasisPoints,
+    circuitBreakerBasisPoints,
+    circuitBreakerWindowSlots,
+    circuitBreakerCooldownSlots,
+    maximumSwapFeeBasisPoints,
+    volatilityReferenceBasisPoints,
+    volatilityHalfLife,
+    limitOrderKeeperTip,
+    maximumSellTaxBasisPoints,
+    estimatedRaydiumCpPoolFee: getEstimatedRaydiumCpPoolCreationFee(),
+  };
+
   it("Initialize zeroboost config account", async () => {
     const { pubkeys, signature } = await initializeConfig(
       program,
       program.provider.publicKey!,
-      {
-        metadataCreationFee,
-        migrationPercentageFee,
-        minimumCurveUsdValuation,
-        maximumCurveUsdValuation,
-        estimatedRaydiumCpPoolFee: getEstimatedRaydiumCpPoolCreationFee(),
-      }
+      configParams
     ).rpcAndKeys();
 
     console.log("config=", signature);
@@ -87,9 +193,21 @@ describe("zeroboost", async () => {
     );
   });
 
+  it("Set pair oracle", async () => {
+    const signature = await setPairOracle(
+      program,
+      NATIVE_MINT,
+      program.provider.publicKey!,
+      SOL_USD_FEED
+    ).rpc();
+
+    console.log("pair oracle=", signature);
+  });
+
   it("Create mint and curve info", async () => {
     const instructions = await mintToken(
       program,
+      mint,
       NATIVE_MINT,
       program.provider.publicKey!,
       {
@@ -102,6 +220,16 @@ describe("zeroboost", async () => {
         migrationTarget: {
           raydium: {},
         },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
       },
       SOL_USD_FEED
     ).instruction();
@@ -113,7 +241,9 @@ describe("zeroboost", async () => {
         })
       )
       .add(instructions);
-    const signature = await program.provider!.sendAndConfirm!(transaction);
+    const signature = await program.provider!.sendAndConfirm!(transaction, [
+      mintKeypair,
+    ]);
 
     console.log("mint=", signature);
 
@@ -138,36 +268,3092 @@ describe("zeroboost", async () => {
     );
   });
 
+  it("Reject self referral", async () => {
+    const payer = program.provider.publicKey!;
+
+    await registerReferrer(program, NATIVE_MINT, payer).rpc();
+
+    await expectError(
+      (
+        await swap(
+          program,
+          mint,
+          payer,
+          {
+            amount: new BN(web3.LAMPORTS_PER_SOL / 100),
+            tradeDirection: 0,
+            native: null,
+            amm: null,
+            presaleProof: null,
+          },
+          payer
+        )
+      ).rpc(),
+      "InvalidReferral"
+    );
+  });
+
+  const token2022MintKeypair = web3.Keypair.generate();
+  const token2022Mint = token2022MintKeypair.publicKey;
+
+  it("Create mint at the name, symbol and creator address", async () => {
+    const creator = program.provider.publicKey!;
+    const params = {
+      name: "Derived",
+      symbol: "DERIVED",
+      uri,
+      decimals,
+      liquidityPercentage,
+      supply: new BN(supply.toString()),
+      migrationTarget: {
+        raydium: {},
+      },
+      launchProtection: null,
+      restrictSameSlotSells: false,
+      sellTaxBasisPoints: 0,
+      initialBuyAmount: null,
+      initialBuyPresaleProof: null,
+      tradingStartsAt: null,
+      presale: null,
+      vesting: null,
+      revokeAuthorities: false,
+      transferFee: null,
+    };
+
+    // Without its signature a mint has to be the derived address
+    const squattedMint = web3.Keypair.generate().publicKey;
+    const squattedInstructions = await mintToken(
+      program,
+      squattedMint,
+      NATIVE_MINT,
+      creator,
+      params,
+      SOL_USD_FEED
+    ).instruction();
+    squattedInstructions.keys = squattedInstructions.keys.filter(
+      ({ pubkey, isSigner }) => !(isSigner && pubkey.equals(squattedMint))
+    );
+
+    await expectError(
+      program.provider.sendAndConfirm!(
+        new web3.Transaction()
+          .add(
+            web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 })
+          )
+          .add(squattedInstructions)
+      ),
+      "InvalidMint"
+    );
+
+    const instructions = await mintToken(
+      program,
+      null,
+      NATIVE_MINT,
+      creator,
+      params,
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions)
+    );
+
+    const [derivedMint] = getMintPda(
+      params.name,
+      params.symbol,
+      creator,
+      program.programId
+    );
+    const { boundingCurve: derivedBoundingCurve } = getBoundingCurveConfig(
+      derivedMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const { mint: curveMint } = await program.account.boundingCurve.fetch(
+      derivedBoundingCurve
+    );
+    const { supply: mintSupply } = await getMint(
+      program.provider.connection,
+      derivedMint
+    );
+
+    expect(curveMint.toBase58()).equal(
+      derivedMint.toBase58(),
+      "Curve not derived from the mint address"
+    );
+    expect(mintSupply.toString()).equal(
+      supply.toString(),
+      "Invalid derived mint supply"
+    );
+  });
+
+  it("Create token 2022 mint with metadata extension", async () => {
+    const instructions = await mintToken(
+      program,
+      token2022Mint,
+      NATIVE_MINT,
+      program.provider.publicKey!,
+      {
+        name,
+        symbol,
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: true,
+        transferFee: null,
+      },
+      SOL_USD_FEED,
+      TOKEN_2022_PROGRAM_ID
+    ).instruction();
+
+    const transaction = new web3.Transaction()
+      .add(
+        web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 300_000,
+        })
+      )
+      .add(instructions);
+    const signature = await program.provider!.sendAndConfirm!(transaction, [
+      token2022MintKeypair,
+    ]);
+
+    console.log("token 2022 mint=", signature);
+
+    const metadata = await getTokenMetadata(
+      program.provider.connection,
+      token2022Mint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expect(metadata!.name).equal(name, "Invalid metadata name");
+    expect(metadata!.symbol).equal(symbol, "Invalid metadata symbol");
+    expect(metadata!.uri).equal(uri, "Invalid metadata uri");
+
+    const mintInfo = await getMint(
+      program.provider.connection,
+      token2022Mint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expect(mintInfo.mintAuthority).equal(null, "Mint authority not revoked");
+    expect(mintInfo.freezeAuthority).equal(null, "Freeze authority not revoked");
+  });
+
+  it("Update token 2022 metadata", async () => {
+    const updatedUri = "https://fliedlice.xyz/metadata.json";
+
+    const signature = await (
+      await updateMetadata(
+        program,
+        token2022Mint,
+        program.provider.publicKey!,
+        { name: null, symbol: null, uri: updatedUri }
+      )
+    ).rpc();
+
+    console.log("update metadata=", signature);
+
+    const metadata = await getTokenMetadata(
+      program.provider.connection,
+      token2022Mint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expect(metadata!.name).equal(name, "Invalid metadata name");
+    expect(metadata!.uri).equal(updatedUri, "Invalid metadata uri");
+  });
+
+  it("Price a transfer fee mint on what the reserve receives", async () => {
+    const payer = program.provider.publicKey!;
+    const connection = program.provider.connection;
+    const transferFeeBasisPoints = 100;
+
+    const feeMintKeypair = web3.Keypair.generate();
+    const feeMint = feeMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      feeMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Fee",
+        symbol: "FEE",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: {
+          basisPoints: transferFeeBasisPoints,
+          maximumFee: new BN(supply.toString()),
+        },
+      },
+      SOL_USD_FEED,
+      TOKEN_2022_PROGRAM_ID
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 }))
+        .add(instructions),
+      [feeMintKeypair]
+    );
+
+    const { boundingCurve: feeBoundingCurve, boundingCurveReserveAta } =
+      getBoundingCurveConfig(
+        feeMint,
+        NATIVE_MINT,
+        program.programId,
+        TOKEN_2022_PROGRAM_ID
+      );
+    const launchedCurveInfo = await program.account.boundingCurve.fetch(
+      feeBoundingCurve
+    );
+    const { amount: launchedReserveBalance } = await getAccount(
+      connection,
+      boundingCurveReserveAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expect(launchedReserveBalance.toString()).equal(
+      launchedCurveInfo.virtualTokenBalance.toString(),
+      "Reserve does not hold the full curve supply"
+    );
+
+    await (
+      await swap(program, feeMint, payer, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const { amount: tokenBalance } = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(
+        feeMint,
+        payer,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const { amount: reserveBalanceBefore } = await getAccount(
+      connection,
+      boundingCurveReserveAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const signature = await (
+      await swap(program, feeMint, payer, {
+        amount: new BN(tokenBalance.toString()),
+        tradeDirection: 1,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    console.log("sell transfer fee mint=", signature);
+
+    const boundingCurveInfo = await program.account.boundingCurve.fetch(
+      feeBoundingCurve
+    );
+    const { amount: reserveBalanceAfter } = await getAccount(
+      connection,
+      boundingCurveReserveAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    // Token 2022 rounds the transfer fee up
+    const transferFee =
+      (tokenBalance * BigInt(transferFeeBasisPoints) + BigInt(9_999)) /
+      BigInt(10_000);
+
+    expect((reserveBalanceAfter - reserveBalanceBefore).toString()).equal(
+      (tokenBalance - transferFee).toString(),
+      "Reserve did not receive the amount net of the transfer fee"
+    );
+    expect(boundingCurveInfo.virtualTokenBalance.toString()).equal(
+      reserveBalanceAfter.toString(),
+      "Virtual token balance does not match the reserve"
+    );
+  });
+
+  it("Quote buy", async () => {
+    const boundingCurveInfo = await program.account.boundingCurve.fetch(
+      boundingCurve
+    );
+
+    const quote = await (
+      await quoteSwap(
+        program,
+        boundingCurveInfo.mint,
+        program.provider.publicKey!,
+        {
+          amount: boundingCurveInfo.maximumPairBalance.muln(2),
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        }
+      )
+    ).view();
+
+    expect(quote.tokenAmount.eq(boundingCurveInfo.virtualTokenBalance)).equal(
+      true,
+      "Invalid quoted amount"
+    );
+    expect(quote.migrationTriggered).equal(true, "Migration not quoted");
+  });
+
   it("Buy minted token", async () => {
     const boundingCurveInfo = await program.account.boundingCurve.fetch(
       boundingCurve
     );
+    const { creatorFeeVault } = getBoundingCurveConfig(
+      mint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const protocolFeePairAta = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
+    );
+
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          program.provider.publicKey!,
+          protocolFeePairAta,
+          devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER,
+          NATIVE_MINT
+        )
+      )
+    );
 
+    const protocolFeeBefore = (
+      await getAccount(program.provider.connection, protocolFeePairAta)
+    ).amount;
+
+    const amount = boundingCurveInfo.maximumPairBalance;
     const signature = await (
       await swap(program, boundingCurveInfo.mint, program.provider.publicKey!, {
-        amount: boundingCurveInfo.maximumPairBalance,
+        amount,
         tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
       })
     ).rpc();
 
     console.log("buy=", signature);
+
+    const fee = amount.muln(swapFeeBasisPoints).divn(10_000);
+    const creatorFee = fee.muln(creatorFeeShareBasisPoints).divn(10_000);
+
+    const creatorFeeVaultInfo = await getAccount(
+      program.provider.connection,
+      creatorFeeVault
+    );
+    const protocolFeeAfter = (
+      await getAccount(program.provider.connection, protocolFeePairAta)
+    ).amount;
+
+    expect(creatorFeeVaultInfo.amount.toString()).equal(
+      creatorFee.toString(),
+      "Invalid creator fee share"
+    );
+    expect((protocolFeeAfter - protocolFeeBefore).toString()).equal(
+      fee.sub(creatorFee).toString(),
+      "Invalid protocol fee share"
+    );
   });
 
-  it("Migrate fund", async () => {
-    const instructions = await (
-      await migrateFund(program, boundingCurve, program.provider.publicKey!, {
-        openTime: new BN(0),
-      })
+  it("Claim creator fees", async () => {
+    const { creatorFeeVault } = getBoundingCurveConfig(
+      mint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const creatorPairAta = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      program.provider.publicKey!
+    );
+
+    const vaultBefore = (
+      await getAccount(program.provider.connection, creatorFeeVault)
+    ).amount;
+    const creatorBefore = (
+      await getAccount(program.provider.connection, creatorPairAta)
+    ).amount;
+
+    const signature = await (
+      await claimCreatorFees(program, mint, program.provider.publicKey!)
+    ).rpc();
+
+    console.log("claim=", signature);
+
+    const vaultAfter = (
+      await getAccount(program.provider.connection, creatorFeeVault)
+    ).amount;
+    const creatorAfter = (
+      await getAccount(program.provider.connection, creatorPairAta)
+    ).amount;
+
+    expect(vaultAfter.toString()).equal("0", "Creator fee vault not drained");
+    expect((creatorAfter - creatorBefore).toString()).equal(
+      vaultBefore.toString(),
+      "Invalid claimed creator fee"
+    );
+  });
+
+  it("Pay referral share to a registered referrer", async () => {
+    const payer = program.provider.publicKey!;
+    const referrer = web3.Keypair.generate();
+
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: payer,
+          toPubkey: referrer.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL / 10,
+        })
+      )
+    );
+    await registerReferrer(program, NATIVE_MINT, referrer.publicKey)
+      .signers([referrer])
+      .rpc();
+
+    const referredMintKeypair = web3.Keypair.generate();
+    const referredMint = referredMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      referredMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Referred",
+        symbol: "REFERRED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
     ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [referredMintKeypair]
+    );
 
-    const transaction = new web3.Transaction();
-    transaction.add(
-      web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 })
+    const amount = new BN(web3.LAMPORTS_PER_SOL / 10);
+
+    await (
+      await swap(
+        program,
+        referredMint,
+        payer,
+        { amount, tradeDirection: 0, native: null, amm: null },
+        referrer.publicKey
+      )
+    ).rpc();
+
+    const fee = amount.muln(swapFeeBasisPoints).divn(10_000);
+    const referralFee = fee.muln(referralFeeShareBasisPoints).divn(10_000);
+
+    const referrerPairAta = await getAccount(
+      program.provider.connection,
+      getAssociatedTokenAddressSync(NATIVE_MINT, referrer.publicKey)
     );
-    transaction.add(instructions);
+    const referral = await program.account.referral.fetch(
+      getReferralPda(referrer.publicKey, NATIVE_MINT, program.programId)[0]
+    );
+
+    expect(referrerPairAta.amount.toString()).equal(
+      referralFee.toString(),
+      "Invalid referral share"
+    );
+    expect(referral.totalFees.toString()).equal(
+      referralFee.toString(),
+      "Invalid referral total fees"
+    );
+    expect(referral.totalTrades.toNumber()).equal(1, "Invalid referral trades");
+  });
 
-    const tx = await program.provider.sendAndConfirm!(transaction);
+  it("Keep swap fee unchanged without a referral", async () => {
+    const payer = program.provider.publicKey!;
 
-    console.log("migrate=", tx);
+    const unreferredMintKeypair = web3.Keypair.generate();
+    const unreferredMint = unreferredMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      unreferredMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Unreferred",
+        symbol: "UNREFERRED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [unreferredMintKeypair]
+    );
+
+    const { creatorFeeVault } = getBoundingCurveConfig(
+      unreferredMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const protocolFeePairAta = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
+    );
+    const protocolFeeBefore = (
+      await getAccount(program.provider.connection, protocolFeePairAta)
+    ).amount;
+
+    const amount = new BN(web3.LAMPORTS_PER_SOL / 10);
+    await (
+      await swap(program, unreferredMint, payer, {
+        amount,
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const fee = amount.muln(swapFeeBasisPoints).divn(10_000);
+    const creatorFee = fee.muln(creatorFeeShareBasisPoints).divn(10_000);
+
+    const creatorFeeVaultInfo = await getAccount(
+      program.provider.connection,
+      creatorFeeVault
+    );
+    const protocolFeeAfter = (
+      await getAccount(program.provider.connection, protocolFeePairAta)
+    ).amount;
+
+    expect(creatorFeeVaultInfo.amount.toString()).equal(
+      creatorFee.toString(),
+      "Invalid creator fee share"
+    );
+    expect((protocolFeeAfter - protocolFeeBefore).toString()).equal(
+      fee.sub(creatorFee).toString(),
+      "Referral share taken without a referral"
+    );
+  });
+
+  it("Sell to native SOL", async () => {
+    const payer = program.provider.publicKey!;
+    const connection = program.provider.connection;
+
+    const nativeMintKeypair = web3.Keypair.generate();
+    const nativeMint = nativeMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      nativeMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Native",
+        symbol: "NATIVE",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [nativeMintKeypair]
+    );
+
+    await (
+      await swap(program, nativeMint, payer, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const { amount: tokenBalance } = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nativeMint, payer)
+    );
+    const lamportsBefore = await connection.getBalance(payer, "confirmed");
+
+    const signature = await (
+      await swap(program, nativeMint, payer, {
+        amount: new BN(tokenBalance.toString()),
+        tradeDirection: 1,
+        native: true,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc({ commitment: "confirmed" });
+
+    console.log("sell native=", signature);
+
+    const transaction = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const [swapEvent] = [
+      ...new EventParser(
+        program.programId,
+        new BorshCoder(program.idl)
+      ).parseLogs(transaction!.meta!.logMessages!),
+    ].filter((event) => event.name === "SwapEvent");
+
+    const lamportsAfter = await connection.getBalance(payer, "confirmed");
+    const [payerNativePair] = getPayerNativePairPda(payer, program.programId);
+
+    expect(lamportsAfter - lamportsBefore + transaction!.meta!.fee).equal(
+      swapEvent.data.pairAmount.toNumber(),
+      "Invalid native sell proceeds"
+    );
+    expect(await connection.getAccountInfo(payerNativePair)).equal(
+      null,
+      "Temporary wrapped account not closed"
+    );
+  });
+
+  it("Sell to native SOL when the native pair account is pre-funded", async () => {
+    const payer = program.provider.publicKey!;
+    const connection = program.provider.connection;
+    const [payerNativePair] = getPayerNativePairPda(payer, program.programId);
+
+    await (
+      await swap(program, mint, payer, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 100),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    // Anyone can send lamports to the derived address ahead of the sell
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: payer,
+          toPubkey: payerNativePair,
+          lamports: web3.LAMPORTS_PER_SOL / 1000,
+        })
+      )
+    );
+
+    const { amount: tokenBalance } = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(mint, payer)
+    );
+
+    await (
+      await swap(program, mint, payer, {
+        amount: new BN(tokenBalance.toString()).divn(2),
+        tradeDirection: 1,
+        native: true,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    expect(await connection.getAccountInfo(payerNativePair)).equal(
+      null,
+      "Temporary wrapped account not closed"
+    );
+  });
+
+  it("Launch and trade on an SPL token pair", async () => {
+    const payer = program.provider.publicKey!;
+    const connection = program.provider.connection;
+    const usdc = web3.Keypair.generate();
+    const payerUsdcAta = getAssociatedTokenAddressSync(usdc.publicKey, payer);
+    const protocolFeeUsdcAta = getAssociatedTokenAddressSync(
+      usdc.publicKey,
+      devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER
+    );
+
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(
+          web3.SystemProgram.createAccount({
+            fromPubkey: payer,
+            newAccountPubkey: usdc.publicKey,
+            space: MINT_SIZE,
+            lamports: await getMinimumBalanceForRentExemptMint(connection),
+            programId: TOKEN_PROGRAM_ID,
+          })
+        )
+        .add(createInitializeMint2Instruction(usdc.publicKey, 6, payer, null))
+        .add(
+          createAssociatedTokenAccountIdempotentInstruction(
+            payer,
+            payerUsdcAta,
+            payer,
+            usdc.publicKey
+          )
+        )
+        .add(
+          createAssociatedTokenAccountIdempotentInstruction(
+            payer,
+            protocolFeeUsdcAta,
+            devnet.ZERO_BOOST_MIGRATION_FEE_RECIEVER,
+            usdc.publicKey
+          )
+        )
+        .add(
+          createMintToInstruction(
+            usdc.publicKey,
+            payerUsdcAta,
+            payer,
+            1_000_000 * 10 ** 6
+          )
+        ),
+      [usdc]
+    );
+
+    await setPairOracle(program, usdc.publicKey, payer, USDC_USD_FEED).rpc();
+
+    const stableMintKeypair = web3.Keypair.generate();
+    const stableMint = stableMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      stableMint,
+      usdc.publicKey,
+      payer,
+      {
+        name: "Stable",
+        symbol: "STABLE",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      USDC_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [stableMintKeypair]
+    );
+
+    const { boundingCurve: stableBoundingCurve, boundingCurveReservePairAta } =
+      getBoundingCurveConfig(stableMint, usdc.publicKey, program.programId);
+    const { virtualPairBalance: initialPairBalance } =
+      await program.account.boundingCurve.fetch(stableBoundingCurve);
+
+    await (
+      await swap(program, stableMint, payer, {
+        amount: new BN(100 * 10 ** 6),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const payerStableAta = getAssociatedTokenAddressSync(stableMint, payer);
+    const { amount: tokenBalance } = await getAccount(connection, payerStableAta);
+    const usdcBalanceBefore = (await getAccount(connection, payerUsdcAta)).amount;
+
+    expect(tokenBalance > BigInt(0)).equal(true, "Buy paid no tokens out");
+
+    await (
+      await swap(program, stableMint, payer, {
+        amount: new BN(tokenBalance.toString()),
+        tradeDirection: 1,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const usdcBalanceAfter = (await getAccount(connection, payerUsdcAta)).amount;
+    const reservePairAta = await getAccount(connection, boundingCurveReservePairAta);
+    const { pair, virtualPairBalance } = await program.account.boundingCurve.fetch(
+      stableBoundingCurve
+    );
+
+    expect(pair.toBase58()).equal(usdc.publicKey.toBase58(), "Invalid curve pair");
+    expect(usdcBalanceAfter > usdcBalanceBefore).equal(
+      true,
+      "Sell paid no pair tokens out"
+    );
+    expect(reservePairAta.amount.toString()).equal(
+      virtualPairBalance.sub(initialPairBalance).toString(),
+      "Reserve out of sync with the curve pair balance"
+    );
+  });
+
+  it("Reject a launch whose feed does not match the pair oracle", async () => {
+    const mismatchMintKeypair = web3.Keypair.generate();
+
+    await expectError(
+      mintToken(
+        program,
+        mismatchMintKeypair.publicKey,
+        NATIVE_MINT,
+        program.provider.publicKey!,
+        {
+          name: "Mismatch",
+          symbol: "MISMATCH",
+          uri,
+          decimals,
+          liquidityPercentage,
+          supply: new BN(supply.toString()),
+          migrationTarget: {
+            raydium: {},
+          },
+          launchProtection: null,
+          restrictSameSlotSells: false,
+          sellTaxBasisPoints: 0,
+          initialBuyAmount: null,
+          initialBuyPresaleProof: null,
+          tradingStartsAt: null,
+          presale: null,
+          vesting: null,
+          revokeAuthorities: false,
+          transferFee: null,
+        },
+        USDC_USD_FEED
+      )
+        .preInstructions([
+          web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }),
+        ])
+        .signers([mismatchMintKeypair])
+        .rpc(),
+      "InvalidFeedAccount"
+    );
+  });
+
+  it("Enforce launch protection caps on buys", async () => {
+    const payer = program.provider.publicKey!;
+
+    const protectedMintKeypair = web3.Keypair.generate();
+    const protectedMint = protectedMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      protectedMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Protected",
+        symbol: "PROTECTED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: {
+          duration: maximumProtectionDuration,
+          transactionCapBasisPoints: minimumProtectionCapBasisPoints,
+          walletCapBasisPoints: minimumProtectionCapBasisPoints,
+        },
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [protectedMintKeypair]
+    );
+
+    const { boundingCurve: protectedBoundingCurve } = getBoundingCurveConfig(
+      protectedMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const { maximumPairBalance } = await program.account.boundingCurve.fetch(
+      protectedBoundingCurve
+    );
+
+    await expectError(
+      (
+        await quoteSwap(program, protectedMint, payer, {
+          amount: maximumPairBalance.divn(100),
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).view(),
+      "ProtectionTransactionCapExceeded"
+    );
+
+    await expectError(
+      (
+        await swap(program, protectedMint, payer, {
+          amount: maximumPairBalance.divn(100),
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).rpc(),
+      "ProtectionTransactionCapExceeded"
+    );
+
+    await (
+      await swap(program, protectedMint, payer, {
+        amount: maximumPairBalance.divn(20_000),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const [trader] = getTraderPda(protectedBoundingCurve, payer, program.programId);
+    const { protectedBuyAmount } = await program.account.trader.fetch(trader);
+    const { amount: tokenBalance } = await getAccount(
+      program.provider.connection,
+      getAssociatedTokenAddressSync(protectedMint, payer)
+    );
+
+    expect(protectedBuyAmount.toString()).equal(
+      tokenBalance.toString(),
+      "Buy within the caps not tracked by the trader"
+    );
+  });
+
+  it("Reject selling in the same slot as a buy", async () => {
+    const payer = program.provider.publicKey!;
+
+    const restrictedMintKeypair = web3.Keypair.generate();
+    const restrictedMint = restrictedMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      restrictedMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Restricted",
+        symbol: "RESTRICTED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: true,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [restrictedMintKeypair]
+    );
+
+    const payerAta = getAssociatedTokenAddressSync(restrictedMint, payer);
+    const buy = await (
+      await swap(program, restrictedMint, payer, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).instruction();
+    const sameSlotSell = await (
+      await swap(program, restrictedMint, payer, {
+        amount: new BN(1),
+        tradeDirection: 1,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).instruction();
+
+    // One transaction lands in a single slot, so the sell follows the buy in the same slot
+    await expectError(
+      program.provider.sendAndConfirm!(
+        new web3.Transaction().add(buy).add(sameSlotSell)
+      ),
+      "SameSlotRoundTrip"
+    );
+
+    await program.provider.sendAndConfirm!(new web3.Transaction().add(buy));
+
+    const { amount: tokenBalance } = await getAccount(
+      program.provider.connection,
+      payerAta
+    );
+    await (
+      await swap(program, restrictedMint, payer, {
+        amount: new BN(tokenBalance.toString()),
+        tradeDirection: 1,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const { amount: tokenBalanceAfter } = await getAccount(
+      program.provider.connection,
+      payerAta
+    );
+
+    expect(tokenBalanceAfter.toString()).equal(
+      "0",
+      "Sell in a later slot rejected"
+    );
+  });
+
+  it("Pause a curve with the circuit breaker until the cooldown ends", async () => {
+    const payer = program.provider.publicKey!;
+    const connection = program.provider.connection;
+
+    await initializeConfig(program, payer, {
+      ...configParams,
+      circuitBreakerBasisPoints: 100,
+      circuitBreakerCooldownSlots: new BN(10),
+    }).rpc();
+
+    try {
+      const breakerMintKeypair = web3.Keypair.generate();
+      const breakerMint = breakerMintKeypair.publicKey;
+
+      const instructions = await mintToken(
+        program,
+        breakerMint,
+        NATIVE_MINT,
+        payer,
+        {
+          name: "Breaker",
+          symbol: "BREAKER",
+          uri,
+          decimals,
+          liquidityPercentage,
+          supply: new BN(supply.toString()),
+          migrationTarget: {
+            raydium: {},
+          },
+          launchProtection: null,
+          restrictSameSlotSells: false,
+          sellTaxBasisPoints: 0,
+          initialBuyAmount: null,
+          initialBuyPresaleProof: null,
+          tradingStartsAt: null,
+          presale: null,
+          vesting: null,
+          revokeAuthorities: false,
+          transferFee: null,
+        },
+        SOL_USD_FEED
+      ).instruction();
+      await program.provider.sendAndConfirm!(
+        new web3.Transaction()
+          .add(
+            web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 })
+          )
+          .add(instructions),
+        [breakerMintKeypair]
+      );
+
+      const { boundingCurve: breakerBoundingCurve } = getBoundingCurveConfig(
+        breakerMint,
+        NATIVE_MINT,
+        program.programId
+      );
+      const { maximumPairBalance } = await program.account.boundingCurve.fetch(
+        breakerBoundingCurve
+      );
+
+      // A tenth of the curve moves the pair reserve well past 1% of its window start balance
+      await (
+        await swap(program, breakerMint, payer, {
+          amount: maximumPairBalance.divn(10),
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).rpc();
+
+      const { pausedUntilSlot } = await program.account.boundingCurve.fetch(
+        breakerBoundingCurve
+      );
+
+      await expectError(
+        (
+          await swap(program, breakerMint, payer, {
+            amount: new BN(web3.LAMPORTS_PER_SOL / 100),
+            tradeDirection: 0,
+            native: null,
+            amm: null,
+            presaleProof: null,
+          })
+        ).rpc(),
+        "CircuitBreakerTripped"
+      );
+
+      while ((await connection.getSlot()) < pausedUntilSlot.toNumber()) {
+        await new Promise((resolve) => setTimeout(resolve, 400));
+      }
+
+      await (
+        await swap(program, breakerMint, payer, {
+          amount: new BN(web3.LAMPORTS_PER_SOL / 100),
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).rpc();
+    } finally {
+      await initializeConfig(program, payer, configParams).rpc();
+    }
+  });
+
+  it("Scale the swap fee with curve volatility", async () => {
+    const payer = program.provider.publicKey!;
+
+    const volatileMintKeypair = web3.Keypair.generate();
+    const volatileMint = volatileMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      volatileMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Volatile",
+        symbol: "VOLATILE",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [volatileMintKeypair]
+    );
+
+    const { boundingCurve: volatileBoundingCurve, creatorFeeVault } =
+      getBoundingCurveConfig(volatileMint, NATIVE_MINT, program.programId);
+    const { maximumPairBalance } = await program.account.boundingCurve.fetch(
+      volatileBoundingCurve
+    );
+    const amount = new BN(web3.LAMPORTS_PER_SOL / 100);
+
+    // The creator share is a fixed part of the swap fee, so its growth tracks the fee
+    const buyCreatorFee = async () => {
+      const before = (
+        await getAccount(program.provider.connection, creatorFeeVault)
+      ).amount;
+      await (
+        await swap(program, volatileMint, payer, {
+          amount,
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).rpc();
+      const after = (
+        await getAccount(program.provider.connection, creatorFeeVault)
+      ).amount;
+      return after - before;
+    };
+
+    const calmCreatorFee = await buyCreatorFee();
+
+    await (
+      await swap(program, volatileMint, payer, {
+        amount: maximumPairBalance.divn(10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const volatileCreatorFee = await buyCreatorFee();
+    const maximumCreatorFee = amount
+      .muln(maximumSwapFeeBasisPoints)
+      .divn(10_000)
+      .muln(creatorFeeShareBasisPoints)
+      .divn(10_000);
+
+    expect(volatileCreatorFee > calmCreatorFee).equal(
+      true,
+      "Fee not raised after a large price move"
+    );
+    expect(volatileCreatorFee <= BigInt(maximumCreatorFee.toString())).equal(
+      true,
+      "Fee above the maximum swap fee"
+    );
+  });
+
+  it("Place and fill a limit order", async () => {
+    const connection = program.provider.connection;
+    const owner = program.provider.publicKey!;
+    const keeper = web3.Keypair.generate();
+
+    const orderMintKeypair = web3.Keypair.generate();
+    const orderMint = orderMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      orderMint,
+      NATIVE_MINT,
+      owner,
+      {
+        name: "Filled",
+        symbol: "FILLED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [orderMintKeypair]
+    );
+
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(keeper.publicKey, web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+
+    const { boundingCurve: orderBoundingCurve } = getBoundingCurveConfig(
+      orderMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const id = new BN(1);
+    const [limitOrder] = getLimitOrderPda(
+      orderBoundingCurve,
+      owner,
+      id,
+      program.programId
+    );
+    const ownerAta = getAssociatedTokenAddressSync(orderMint, owner);
+
+    await (
+      await placeLimitOrder(program, orderMint, owner, {
+        id,
+        tradeDirection: 0,
+        amountIn: new BN(web3.LAMPORTS_PER_SOL / 10),
+        minimumAmountOut: new BN(1),
+      })
+    ).rpc();
+
+    const keeperBalance = await connection.getBalance(keeper.publicKey);
+
+    const signature = await (
+      await fillLimitOrder(program, orderMint, owner, id, keeper.publicKey)
+    )
+      .signers([keeper])
+      .rpc();
+
+    console.log("fill limit order=", signature);
+
+    const { virtualTokenBalance, initialSupply } =
+      await program.account.boundingCurve.fetch(orderBoundingCurve);
+
+    expect(await program.account.limitOrder.fetchNullable(limitOrder)).equal(
+      null,
+      "Limit order not closed"
+    );
+    expect((await getAccount(connection, ownerAta)).amount > BigInt(0)).equal(
+      true,
+      "Limit order proceeds not paid to owner"
+    );
+    expect(virtualTokenBalance.lt(initialSupply)).equal(
+      true,
+      "Bounding curve not updated by the fill"
+    );
+    expect(
+      (await connection.getBalance(keeper.publicKey)) - keeperBalance
+    ).equal(limitOrderKeeperTip.toNumber(), "Invalid keeper tip");
+  });
+
+  it("Place and cancel a limit order", async () => {
+    const connection = program.provider.connection;
+    const owner = program.provider.publicKey!;
+
+    const orderMintKeypair = web3.Keypair.generate();
+    const orderMint = orderMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      orderMint,
+      NATIVE_MINT,
+      owner,
+      {
+        name: "Cancelled order",
+        symbol: "CANCELLED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [orderMintKeypair]
+    );
+
+    const { boundingCurve: orderBoundingCurve } = getBoundingCurveConfig(
+      orderMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const id = new BN(2);
+    const amountIn = web3.LAMPORTS_PER_SOL / 10;
+
+    await (
+      await placeLimitOrder(program, orderMint, owner, {
+        id,
+        tradeDirection: 0,
+        amountIn: new BN(amountIn),
+        minimumAmountOut: new BN(supply.toString()),
+      })
+    ).rpc();
+
+    const [limitOrder] = getLimitOrderPda(
+      orderBoundingCurve,
+      owner,
+      id,
+      program.programId
+    );
+    const [limitOrderTrader] = getTraderPda(
+      orderBoundingCurve,
+      limitOrder,
+      program.programId
+    );
+    const limitOrderAta = getAssociatedTokenAddressSync(
+      orderMint,
+      limitOrder,
+      true
+    );
+    const limitOrderPairAta = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      limitOrder,
+      true
+    );
+    const ownerPairAta = getAssociatedTokenAddressSync(NATIVE_MINT, owner);
+
+    const limitOrderInfo = await connection.getAccountInfo(limitOrder);
+    const limitOrderRent = await connection.getMinimumBalanceForRentExemption(
+      limitOrderInfo!.data.length
+    );
+
+    expect(limitOrderInfo!.lamports - limitOrderRent).equal(
+      limitOrderKeeperTip.toNumber(),
+      "Keeper tip not escrowed"
+    );
+
+    const refundedLamports =
+      limitOrderInfo!.lamports +
+      (await connection.getBalance(limitOrderTrader)) +
+      (await connection.getBalance(limitOrderAta)) +
+      (await connection.getBalance(limitOrderPairAta)) -
+      amountIn;
+    const ownerBalance = await connection.getBalance(owner);
+    const ownerPairBalance = (await getAccount(connection, ownerPairAta))
+      .amount;
+
+    const signature = await (
+      await cancelLimitOrder(program, orderMint, owner, id)
+    ).rpc({ commitment: "confirmed" });
+
+    console.log("cancel limit order=", signature);
+
+    const { meta } = (await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    }))!;
+
+    expect(await program.account.limitOrder.fetchNullable(limitOrder)).equal(
+      null,
+      "Limit order not closed"
+    );
+    expect(
+      (await getAccount(connection, ownerPairAta)).amount - ownerPairBalance
+    ).equal(BigInt(amountIn), "Escrow not refunded");
+    expect((await connection.getBalance(owner)) - ownerBalance).equal(
+      refundedLamports - meta!.fee,
+      "Keeper tip and rent not refunded"
+    );
+  });
+
+  it("Route a sell into a buy on another curve", async () => {
+    const owner = program.provider.publicKey!;
+
+    const launch = async (name: string, symbol: string) => {
+      const routeMintKeypair = web3.Keypair.generate();
+      const instructions = await mintToken(
+        program,
+        routeMintKeypair.publicKey,
+        NATIVE_MINT,
+        owner,
+        {
+          name,
+          symbol,
+          uri,
+          decimals,
+          liquidityPercentage,
+          supply: new BN(supply.toString()),
+          migrationTarget: {
+            raydium: {},
+          },
+          launchProtection: null,
+          restrictSameSlotSells: false,
+          sellTaxBasisPoints: 0,
+          initialBuyAmount: null,
+          initialBuyPresaleProof: null,
+          tradingStartsAt: null,
+          presale: null,
+          vesting: null,
+          revokeAuthorities: false,
+          transferFee: null,
+        },
+        SOL_USD_FEED
+      ).instruction();
+      await program.provider.sendAndConfirm!(
+        new web3.Transaction()
+          .add(
+            web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 })
+          )
+          .add(instructions),
+        [routeMintKeypair]
+      );
+      return routeMintKeypair.publicKey;
+    };
+
+    const sourceMint = await launch("Source", "SOURCE");
+    const destinationMint = await launch("Destination", "DESTINATION");
+    const sourceAta = getAssociatedTokenAddressSync(sourceMint, owner);
+    const destinationAta = getAssociatedTokenAddressSync(destinationMint, owner);
+
+    await (
+      await swap(program, sourceMint, owner, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const sourceBalance = (
+      await getAccount(program.provider.connection, sourceAta)
+    ).amount;
+
+    const signature = await (
+      await swapRoute(program, sourceMint, destinationMint, owner, {
+        amount: new BN(sourceBalance.toString()),
+        minimumAmountOut: new BN(1),
+      })
+    ).rpc();
+
+    console.log("route=", signature);
+
+    const { boundingCurve: destinationBoundingCurve } = getBoundingCurveConfig(
+      destinationMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const { virtualTokenBalance, initialSupply } =
+      await program.account.boundingCurve.fetch(destinationBoundingCurve);
+    const destinationBalance = (
+      await getAccount(program.provider.connection, destinationAta)
+    ).amount;
+
+    expect(
+      (await getAccount(program.provider.connection, sourceAta)).amount
+    ).equal(BigInt(0), "Source tokens not sold");
+    expect(destinationBalance > BigInt(0)).equal(
+      true,
+      "Destination tokens not bought"
+    );
+    expect(initialSupply.sub(virtualTokenBalance).toString()).equal(
+      destinationBalance.toString(),
+      "Destination curve not updated by the route"
+    );
+  });
+
+  it("Buyback and burn sell tax on the curve", async () => {
+    const connection = program.provider.connection;
+    const authority = program.provider.publicKey!;
+
+    const taxedMintKeypair = web3.Keypair.generate();
+    const taxedMint = taxedMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      taxedMint,
+      NATIVE_MINT,
+      authority,
+      {
+        name: "Taxed",
+        symbol: "TAXED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 500,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [taxedMintKeypair]
+    );
+
+    const { boundingCurve: taxedBoundingCurve, buybackVault } =
+      getBoundingCurveConfig(taxedMint, NATIVE_MINT, program.programId);
+    const authorityAta = getAssociatedTokenAddressSync(taxedMint, authority);
+
+    await (
+      await swap(program, taxedMint, authority, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+    await (
+      await swap(program, taxedMint, authority, {
+        amount: new BN(
+          (await getAccount(connection, authorityAta)).amount.toString()
+        ),
+        tradeDirection: 1,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const vaultBalance = (await getAccount(connection, buybackVault)).amount;
+    const { supply: mintSupply } = await getMint(connection, taxedMint);
+
+    expect(vaultBalance > BigInt(0)).equal(true, "No sell tax collected");
+
+    await expectError(
+      (
+        await buybackAndBurn(program, taxedMint, authority, {
+          minimumAmountOut: new BN(supply.toString()),
+        })
+      ).rpc(),
+      "SlippageExceeded"
+    );
+
+    const keeper = web3.Keypair.generate();
+
+    await expectError(
+      (
+        await buybackAndBurn(program, taxedMint, keeper.publicKey, {
+          minimumAmountOut: new BN(1),
+        })
+      )
+        .signers([keeper])
+        .rpc(),
+      "InvalidAuthority"
+    );
+
+    const signature = await (
+      await buybackAndBurn(program, taxedMint, authority, {
+        minimumAmountOut: new BN(1),
+      })
+    ).rpc();
+
+    console.log("curve buyback=", signature);
+
+    const { virtualTokenBalance, tradeable } =
+      await program.account.boundingCurve.fetch(taxedBoundingCurve);
+
+    expect(
+      (await getAccount(connection, buybackVault)).amount < vaultBalance
+    ).equal(true, "Buyback vault not spent");
+    expect((await getMint(connection, taxedMint)).supply < mintSupply).equal(
+      true,
+      "Bought back tokens not burned"
+    );
+    expect(virtualTokenBalance.gtn(0)).equal(true, "Curve sold out");
+    expect(tradeable).equal(true, "Buyback triggered migration");
+  });
+
+  it("Buyback and burn sell tax through the pool after migration", async () => {
+    const connection = program.provider.connection;
+    const authority = program.provider.publicKey!;
+
+    const taxedMintKeypair = web3.Keypair.generate();
+    const taxedMint = taxedMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      taxedMint,
+      NATIVE_MINT,
+      authority,
+      {
+        name: "Taxed pool",
+        symbol: "TAXEDPOOL",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 500,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [taxedMintKeypair]
+    );
+
+    const { boundingCurve: taxedBoundingCurve, buybackVault } =
+      getBoundingCurveConfig(taxedMint, NATIVE_MINT, program.programId);
+    const authorityAta = getAssociatedTokenAddressSync(taxedMint, authority);
+
+    await (
+      await swap(program, taxedMint, authority, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+    await (
+      await swap(program, taxedMint, authority, {
+        amount: new BN(
+          (await getAccount(connection, authorityAta)).amount.toString()
+        ),
+        tradeDirection: 1,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const { maximumPairBalance } = await program.account.boundingCurve.fetch(
+      taxedBoundingCurve
+    );
+
+    // Over paying buys out what is left on the curve and triggers the migration
+    await (
+      await swap(program, taxedMint, authority, {
+        amount: maximumPairBalance.muln(2),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const migrateInstructions = await (
+      await migrateFund(program, taxedBoundingCurve, authority, {
+        openTime: new BN(0),
+      })
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 }))
+        .add(migrateInstructions)
+    );
+
+    const { supply: mintSupply } = await getMint(connection, taxedMint);
+
+    const signature = await (
+      await buybackAndBurn(program, taxedMint, authority, {
+        minimumAmountOut: new BN(1),
+      })
+    ).rpc();
+
+    console.log("pool buyback=", signature);
+
+    expect((await getAccount(connection, buybackVault)).amount).equal(
+      BigInt(0),
+      "Buyback vault not spent"
+    );
+    expect((await getMint(connection, taxedMint)).supply < mintSupply).equal(
+      true,
+      "Bought back tokens not burned"
+    );
+  });
+
+  it("Run the creator initial buy inside the launch", async () => {
+    const payer = program.provider.publicKey!;
+
+    const initialMintKeypair = web3.Keypair.generate();
+    const initialMint = initialMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      initialMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Initial",
+        symbol: "INITIAL",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: new BN(web3.LAMPORTS_PER_SOL / 100),
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }))
+        .add(instructions),
+      [initialMintKeypair]
+    );
+
+    const {
+      boundingCurve: initialBoundingCurve,
+      creatorFeeVault: initialCreatorFeeVault,
+    } = getBoundingCurveConfig(initialMint, NATIVE_MINT, program.programId);
+    const { virtualTokenBalance, initialSupply, volatilityAccumulator } =
+      await program.account.boundingCurve.fetch(initialBoundingCurve);
+    const { amount: tokenBalance } = await getAccount(
+      program.provider.connection,
+      getAssociatedTokenAddressSync(initialMint, payer)
+    );
+    const { amount: creatorFees } = await getAccount(
+      program.provider.connection,
+      initialCreatorFeeVault
+    );
+
+    expect(tokenBalance > BigInt(0)).equal(true, "Initial buy not received");
+    expect(initialSupply.sub(virtualTokenBalance).toString()).equal(
+      tokenBalance.toString(),
+      "Initial buy not applied to the curve"
+    );
+    expect(creatorFees > BigInt(0)).equal(true, "Initial buy paid no fee");
+    expect(volatilityAccumulator.gtn(0)).equal(
+      true,
+      "Initial buy not counted towards volatility"
+    );
+  });
+
+  it("Count the initial buy against the launch protection wallet cap", async () => {
+    const creator = program.provider.publicKey!;
+    const { maximumPairBalance } = await program.account.boundingCurve.fetch(
+      boundingCurve
+    );
+    // Caps are 0.1% of the curve, the initial buy takes a twentieth of the wallet cap
+    const capPairAmount = maximumPairBalance.divn(1_000);
+
+    const protectedMintKeypair = web3.Keypair.generate();
+    const protectedMint = protectedMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      protectedMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Protected initial",
+        symbol: "PROTECTEDINITIAL",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: {
+          duration: maximumProtectionDuration,
+          transactionCapBasisPoints: minimumProtectionCapBasisPoints,
+          walletCapBasisPoints: minimumProtectionCapBasisPoints,
+        },
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: capPairAmount.divn(20),
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }))
+        .add(instructions),
+      [protectedMintKeypair]
+    );
+
+    const { boundingCurve: protectedBoundingCurve } = getBoundingCurveConfig(
+      protectedMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const [trader] = getTraderPda(
+      protectedBoundingCurve,
+      creator,
+      program.programId
+    );
+    const { protectedBuyAmount } = await program.account.trader.fetch(trader);
+    const { amount: tokenBalance } = await getAccount(
+      program.provider.connection,
+      getAssociatedTokenAddressSync(protectedMint, creator)
+    );
+
+    expect(protectedBuyAmount.toString()).equal(
+      tokenBalance.toString(),
+      "Initial buy not tracked by the creator trader"
+    );
+
+    await expectError(
+      (
+        await swap(program, protectedMint, creator, {
+          amount: capPairAmount,
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).rpc(),
+      "ProtectionWalletCapExceeded"
+    );
+  });
+
+  it("Require the presale allowlist for the initial buy", async () => {
+    const creator = program.provider.publicKey!;
+    const tree = getPresaleTree([creator, web3.Keypair.generate().publicKey]);
+    const presale = {
+      duration: 300,
+      walletCap: new BN(supply.toString()),
+      tokenCap: new BN(supply.toString()),
+    };
+    const initialBuyAmount = new BN(web3.LAMPORTS_PER_SOL / 100);
+
+    const rejectedMintKeypair = web3.Keypair.generate();
+
+    const rejectedInstructions = await mintToken(
+      program,
+      rejectedMintKeypair.publicKey,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Presale rejected",
+        symbol: "PRESALEREJECTED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: {
+          ...presale,
+          root: getPresaleTree([web3.Keypair.generate().publicKey]).root,
+        },
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+
+    await expectError(
+      program.provider.sendAndConfirm!(
+        new web3.Transaction()
+          .add(
+            web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })
+          )
+          .add(rejectedInstructions),
+        [rejectedMintKeypair]
+      ),
+      "NotAllowlisted"
+    );
+
+    const presaleMintKeypair = web3.Keypair.generate();
+    const presaleMint = presaleMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      presaleMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Presale initial",
+        symbol: "PRESALEINITIAL",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount,
+        initialBuyPresaleProof: tree.getProof(creator),
+        tradingStartsAt: null,
+        presale: { ...presale, root: tree.root },
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }))
+        .add(instructions),
+      [presaleMintKeypair]
+    );
+
+    const { boundingCurve: presaleBoundingCurve } = getBoundingCurveConfig(
+      presaleMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const { presaleSold } = await program.account.boundingCurve.fetch(
+      presaleBoundingCurve
+    );
+    const { amount: tokenBalance } = await getAccount(
+      program.provider.connection,
+      getAssociatedTokenAddressSync(presaleMint, creator)
+    );
+
+    expect(presaleSold.toString()).equal(
+      tokenBalance.toString(),
+      "Initial buy not counted as presale sold"
+    );
+  });
+
+  it("Trigger the migration from an initial buy that fills the curve", async () => {
+    const creator = program.provider.publicKey!;
+    const { maximumPairBalance } = await program.account.boundingCurve.fetch(
+      boundingCurve
+    );
+
+    const filledMintKeypair = web3.Keypair.generate();
+    const filledMint = filledMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      filledMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Filled initial",
+        symbol: "FILLEDINITIAL",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: maximumPairBalance.muln(2),
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }))
+        .add(instructions),
+      [filledMintKeypair]
+    );
+
+    const { boundingCurve: filledBoundingCurve } = getBoundingCurveConfig(
+      filledMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const { tradeable, virtualTokenBalance } =
+      await program.account.boundingCurve.fetch(filledBoundingCurve);
+
+    expect(virtualTokenBalance.toString()).equal("0", "Curve not bought out");
+    expect(tradeable).equal(false, "Migration not triggered");
+  });
+
+  it("Reject trades before the scheduled trading start", async () => {
+    const payer = program.provider.publicKey!;
+    const tradingStartsAt = new BN(Math.floor(Date.now() / 1000) + 300);
+
+    const scheduledMintKeypair = web3.Keypair.generate();
+    const scheduledMint = scheduledMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      scheduledMint,
+      NATIVE_MINT,
+      payer,
+      {
+        name: "Scheduled",
+        symbol: "SCHEDULED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [scheduledMintKeypair]
+    );
+
+    const { boundingCurve: scheduledBoundingCurve } = getBoundingCurveConfig(
+      scheduledMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const boundingCurveInfo = await program.account.boundingCurve.fetch(
+      scheduledBoundingCurve
+    );
+
+    expect(boundingCurveInfo.tradingStartsAt.eq(tradingStartsAt)).equal(
+      true,
+      "Invalid trading start"
+    );
+
+    await expectError(
+      (
+        await swap(program, scheduledMint, payer, {
+          amount: new BN(web3.LAMPORTS_PER_SOL / 100),
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).rpc(),
+      "TradingNotStarted"
+    );
+  });
+
+  it("Enforce the presale allowlist and caps", async () => {
+    const creator = program.provider.publicKey!;
+    const allowlisted = web3.Keypair.generate();
+    const tree = getPresaleTree([creator, allowlisted.publicKey]);
+    const amount = new BN(web3.LAMPORTS_PER_SOL / 10);
+    const buy = (wallet: web3.PublicKey, amount: BN) => ({
+      amount,
+      tradeDirection: 0,
+      native: null,
+      amm: null,
+      presaleProof: tree.getProof(wallet),
+    });
+
+    // Curves share a constant price, so the main curve roughly prices the presale buys
+    const { tokenAmount } = await (
+      await quoteSwap(program, mint, creator, buy(creator, amount))
+    ).view();
+    const cap = tokenAmount.muln(3).divn(2);
+
+    const presaleMintKeypair = web3.Keypair.generate();
+    const presaleMint = presaleMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      presaleMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Presale",
+        symbol: "PRESALE",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: {
+          root: tree.root,
+          duration: 300,
+          walletCap: cap,
+          tokenCap: cap,
+        },
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [presaleMintKeypair]
+    );
+
+    await expectError(
+      (
+        await swap(program, presaleMint, creator, {
+          ...buy(creator, amount),
+          presaleProof: null,
+        })
+      ).rpc(),
+      "NotAllowlisted"
+    );
+    await expectError(
+      (
+        await swap(program, presaleMint, creator, buy(creator, amount.muln(2)))
+      ).rpc(),
+      "PresaleWalletCapExceeded"
+    );
+
+    await (
+      await swap(program, presaleMint, creator, buy(creator, amount))
+    ).rpc();
+
+    const { boundingCurve: presaleBoundingCurve } = getBoundingCurveConfig(
+      presaleMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const { presaleSold } = await program.account.boundingCurve.fetch(
+      presaleBoundingCurve
+    );
+    const { amount: tokenBalance } = await getAccount(
+      program.provider.connection,
+      getAssociatedTokenAddressSync(presaleMint, creator)
+    );
+
+    expect(presaleSold.toString()).equal(
+      tokenBalance.toString(),
+      "Presale buy not counted as presale sold"
+    );
+
+    // Within its own wallet cap, but past what is left of the presale token cap
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: creator,
+          toPubkey: allowlisted.publicKey,
+          lamports: web3.LAMPORTS_PER_SOL / 2,
+        })
+      )
+    );
+    await expectError(
+      (
+        await swap(
+          program,
+          presaleMint,
+          allowlisted.publicKey,
+          buy(allowlisted.publicKey, amount)
+        )
+      )
+        .signers([allowlisted])
+        .rpc(),
+      "PresaleCapExceeded"
+    );
+  });
+
+  it("Claim vested tokens after the cliff", async () => {
+    const creator = program.provider.publicKey!;
+    const amount = new BN(supply.toString()).divn(10);
+
+    const cliffMintKeypair = web3.Keypair.generate();
+    const cliffMint = cliffMintKeypair.publicKey;
+
+    const cliffInstructions = await mintToken(
+      program,
+      cliffMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Cliff",
+        symbol: "CLIFF",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: { amount, cliffDuration: 300, duration: 600 },
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 }))
+        .add(cliffInstructions),
+      [cliffMintKeypair]
+    );
+
+    await expectError(
+      (await claimVested(program, cliffMint, creator)).rpc(),
+      "NothingToClaim"
+    );
+
+    const linearMintKeypair = web3.Keypair.generate();
+    const linearMint = linearMintKeypair.publicKey;
+
+    const linearInstructions = await mintToken(
+      program,
+      linearMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Linear",
+        symbol: "LINEAR",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: { amount, cliffDuration: 0, duration: 300 },
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 }))
+        .add(linearInstructions),
+      [linearMintKeypair]
+    );
+
+    // Without a cliff the allocation unlocks linearly from the launch
+    await new Promise((resolve) => setTimeout(resolve, 3_000));
+    await (await claimVested(program, linearMint, creator)).rpc();
+
+    const { boundingCurve: linearBoundingCurve } = getBoundingCurveConfig(
+      linearMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const [vesting] = getVestingPda(linearBoundingCurve, program.programId);
+    const { claimedAmount } = await program.account.vesting.fetch(vesting);
+    const { amount: claimed } = await getAccount(
+      program.provider.connection,
+      getAssociatedTokenAddressSync(linearMint, creator)
+    );
+
+    expect(claimed > BigInt(0)).equal(true, "Nothing claimed after launch");
+    expect(claimed < BigInt(amount.toString())).equal(
+      true,
+      "Allocation claimed before the vesting end"
+    );
+    expect(claimedAmount.toString()).equal(
+      claimed.toString(),
+      "Claimed amount not tracked"
+    );
+  });
+
+  it("Cancel an untraded launch", async () => {
+    const creator = program.provider.publicKey!;
+
+    await expectError(
+      (await cancelLaunch(program, mint, creator)).rpc(),
+      "AlreadyTraded"
+    );
+
+    const cancelledMintKeypair = web3.Keypair.generate();
+    const cancelledMint = cancelledMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      cancelledMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Cancelled",
+        symbol: "CANCELLED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [cancelledMintKeypair]
+    );
+
+    await (await cancelLaunch(program, cancelledMint, creator)).rpc();
+
+    const { boundingCurve: cancelledBoundingCurve } = getBoundingCurveConfig(
+      cancelledMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const { supply: remainingSupply } = await getMint(
+      program.provider.connection,
+      cancelledMint
+    );
+
+    expect(
+      await program.account.boundingCurve.fetchNullable(cancelledBoundingCurve)
+    ).equal(null, "Bounding curve not closed");
+    expect(remainingSupply.toString()).equal("0", "Launch supply not burned");
+  });
+
+  it("Return donated pair tokens when cancelling a launch", async () => {
+    const connection = program.provider.connection;
+    const creator = program.provider.publicKey!;
+    const dust = 1_000_000;
+
+    const dustMintKeypair = web3.Keypair.generate();
+    const dustMint = dustMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      dustMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Dust",
+        symbol: "DUST",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [dustMintKeypair]
+    );
+
+    const { boundingCurveReservePairAta, creatorFeeVault, buybackVault } =
+      getBoundingCurveConfig(dustMint, NATIVE_MINT, program.programId);
+    const creatorPairAta = getAssociatedTokenAddressSync(NATIVE_MINT, creator);
+
+    const donation = new web3.Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(
+        creator,
+        creatorPairAta,
+        creator,
+        NATIVE_MINT
+      ),
+      web3.SystemProgram.transfer({
+        fromPubkey: creator,
+        toPubkey: creatorPairAta,
+        lamports: dust * 3,
+      }),
+      createSyncNativeInstruction(creatorPairAta)
+    );
+    for (const account of [
+      boundingCurveReservePairAta,
+      creatorFeeVault,
+      buybackVault,
+    ]) {
+      donation.add(
+        createTransferCheckedInstruction(
+          creatorPairAta,
+          NATIVE_MINT,
+          account,
+          creator,
+          dust,
+          9
+        )
+      );
+    }
+    await program.provider.sendAndConfirm!(donation);
+
+    const { amount: creatorPairBefore } = await getAccount(
+      connection,
+      creatorPairAta
+    );
+
+    await (await cancelLaunch(program, dustMint, creator)).rpc();
+
+    const { amount: creatorPairAfter } = await getAccount(
+      connection,
+      creatorPairAta
+    );
+
+    expect((creatorPairAfter - creatorPairBefore).toString()).equal(
+      (dust * 3).toString(),
+      "Donated pair tokens not returned"
+    );
+    for (const account of [
+      boundingCurveReservePairAta,
+      creatorFeeVault,
+      buybackVault,
+    ]) {
+      expect(await connection.getAccountInfo(account)).equal(
+        null,
+        "Pair side account not closed"
+      );
+    }
+  });
+
+  it("Cancel an untraded launch and burn the vesting escrow", async () => {
+    const connection = program.provider.connection;
+    const creator = program.provider.publicKey!;
+
+    const vestedMintKeypair = web3.Keypair.generate();
+    const vestedMint = vestedMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      vestedMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Cancelled vesting",
+        symbol: "CANCELLEDVESTING",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: {
+          amount: new BN(supply.toString()).divn(10),
+          cliffDuration: 0,
+          duration: 300,
+        },
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [vestedMintKeypair]
+    );
+
+    const { boundingCurve: vestedBoundingCurve } = getBoundingCurveConfig(
+      vestedMint,
+      NATIVE_MINT,
+      program.programId
+    );
+    const [vesting] = getVestingPda(vestedBoundingCurve, program.programId);
+
+    await expectError(
+      (await cancelLaunch(program, vestedMint, creator))
+        .accounts({ vestingAta: null })
+        .rpc(),
+      "MissingVestingAccounts"
+    );
+
+    const signature = await (
+      await cancelLaunch(program, vestedMint, creator)
+    ).rpc();
+
+    console.log("cancel launch=", signature);
+
+    expect(await connection.getAccountInfo(vestedBoundingCurve)).equal(
+      null,
+      "Bounding curve not closed"
+    );
+    expect(await connection.getAccountInfo(vesting)).equal(
+      null,
+      "Vesting not closed"
+    );
+    expect((await getMint(connection, vestedMint)).supply.toString()).equal(
+      "0",
+      "Launch supply not burned"
+    );
+  });
+
+  it("Reject cancelling a launch traded back to its launch balances", async () => {
+    const creator = program.provider.publicKey!;
+
+    const tradedMintKeypair = web3.Keypair.generate();
+    const tradedMint = tradedMintKeypair.publicKey;
+
+    const instructions = await mintToken(
+      program,
+      tradedMint,
+      NATIVE_MINT,
+      creator,
+      {
+        name: "Traded",
+        symbol: "TRADED",
+        uri,
+        decimals,
+        liquidityPercentage,
+        supply: new BN(supply.toString()),
+        migrationTarget: {
+          raydium: {},
+        },
+        launchProtection: null,
+        restrictSameSlotSells: false,
+        sellTaxBasisPoints: 0,
+        initialBuyAmount: null,
+        initialBuyPresaleProof: null,
+        tradingStartsAt: null,
+        presale: null,
+        vesting: null,
+        revokeAuthorities: false,
+        transferFee: null,
+      },
+      SOL_USD_FEED
+    ).instruction();
+    await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 250_000 }))
+        .add(instructions),
+      [tradedMintKeypair]
+    );
+
+    const creatorAta = getAssociatedTokenAddressSync(tradedMint, creator);
+
+    await (
+      await swap(program, tradedMint, creator, {
+        amount: new BN(web3.LAMPORTS_PER_SOL / 10),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+    await (
+      await swap(program, tradedMint, creator, {
+        amount: new BN(
+          (
+            await getAccount(program.provider.connection, creatorAta)
+          ).amount.toString()
+        ),
+        tradeDirection: 1,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    await expectError(
+      (await cancelLaunch(program, tradedMint, creator)).rpc(),
+      "AlreadyTraded"
+    );
+  });
+
+  it("Complete the curve", async () => {
+    const boundingCurveInfo = await program.account.boundingCurve.fetch(
+      boundingCurve
+    );
+
+    expect(boundingCurveInfo.tradeable).equal(
+      true,
+      "Partial buy must not trigger migration"
+    );
+
+    // Over paying buys out what is left on the curve and triggers the migration
+    const signature = await (
+      await swap(program, mint, program.provider.publicKey!, {
+        amount: boundingCurveInfo.maximumPairBalance.muln(2),
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    console.log("complete=", signature);
+
+    const { tradeable, virtualTokenBalance } =
+      await program.account.boundingCurve.fetch(boundingCurve);
+
+    expect(tradeable).equal(false, "Completed curve must stop trading");
+    expect(virtualTokenBalance.toString()).equal("0", "Curve not sold out");
+  });
+
+  it("Migrate fund", async () => {
+    const instructions = await (
+      await migrateFund(program, boundingCurve, program.provider.publicKey!, {
+        openTime: new BN(0),
+      })
+    ).instruction();
+
+    const transaction = new web3.Transaction();
+    transaction.add(
+      web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 })
+    );
+    transaction.add(instructions);
+
+    const tx = await program.provider.sendAndConfirm!(transaction, [], {
+      commitment: "confirmed",
+    });
+
+    console.log("migrate=", tx);
+
+    const mintInfo = await getMint(program.provider.connection, mint);
+    const { meta } = (await program.provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    }))!;
+    const [migrateEvent] = [
+      ...new EventParser(
+        program.programId,
+        new BorshCoder(program.idl)
+      ).parseLogs(meta!.logMessages!),
+    ].filter((event) => event.name === "MigrateEvent");
+
+    expect(mintInfo.mintAuthority).equal(null, "Mint authority not revoked");
+    expect(mintInfo.freezeAuthority).equal(null, "Freeze authority not revoked");
+    expect(migrateEvent.data.mintAuthorityRevoked).equal(
+      true,
+      "Mint authority not reported revoked"
+    );
+    expect(migrateEvent.data.freezeAuthorityRevoked).equal(
+      true,
+      "Freeze authority not reported revoked"
+    );
+  });
+
+  it("Reject a migrated swap without raydium params", async () => {
+    await expectError(
+      (
+        await swap(program, mint, program.provider.publicKey!, {
+          amount: new BN(web3.LAMPORTS_PER_SOL / 100),
+          tradeDirection: 0,
+          native: null,
+          amm: null,
+          presaleProof: null,
+        })
+      ).rpc(),
+      "MissingAmmSwapParams"
+    );
+  });
+
+  it("Buy migrated token through the raydium pool", async () => {
+    const payer = program.provider.publicKey!;
+    const payerAta = getAssociatedTokenAddressSync(mint, payer);
+    const balance = (await getAccount(program.provider.connection, payerAta))
+      .amount;
+    const params = {
+      amount: new BN(web3.LAMPORTS_PER_SOL / 100),
+      tradeDirection: 0,
+      native: null,
+      amm: { baseOutput: false, otherAmountThreshold: new BN(1) },
+      presaleProof: null,
+    };
+
+    const quote = await (await quoteSwap(program, mint, payer, params)).view();
+    const signature = await (await swap(program, mint, payer, params)).rpc();
+
+    console.log("migrated buy=", signature);
+
+    expect(
+      (await getAccount(program.provider.connection, payerAta)).amount - balance
+    ).equal(
+      BigInt(quote.tokenAmount.toString()),
+      "Pool buy does not match quote"
+    );
+  });
+
+  it("Lock metaplex metadata once migrated", async () => {
+    await expectError(
+      (
+        await updateMetadata(program, mint, program.provider.publicKey!, {
+          name: null,
+          symbol: null,
+          uri: "https://fliedlice.xyz/migrated.json",
+        })
+      ).rpc(),
+      "AlreadyMigrated"
+    );
+
+    const umi = createUmi(program.provider.connection);
+    const metadata = await fetchMetadata(
+      umi,
+      findMetadataPda(umi, { mint: publicKey(mint) })
+    );
+
+    expect(metadata.isMutable).equal(false, "Metadata still mutable");
+    expect(metadata.uri).equal(uri, "Metadata changed by migration");
+  });
+
+  it("Lock token 2022 metadata once migrated", async () => {
+    const payer = program.provider.publicKey!;
+    const { boundingCurve: token2022BoundingCurve } = getBoundingCurveConfig(
+      token2022Mint,
+      NATIVE_MINT,
+      program.programId,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const { maximumPairBalance } = await program.account.boundingCurve.fetch(
+      token2022BoundingCurve
+    );
+
+    await (
+      await swap(program, token2022Mint, payer, {
+        amount: maximumPairBalance,
+        tradeDirection: 0,
+        native: null,
+        amm: null,
+        presaleProof: null,
+      })
+    ).rpc();
+
+    const instructions = await (
+      await migrateFund(program, token2022BoundingCurve, payer, {
+        openTime: new BN(0),
+      })
+    ).instruction();
+    const signature = await program.provider.sendAndConfirm!(
+      new web3.Transaction()
+        .add(web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 }))
+        .add(instructions),
+      [],
+      { commitment: "confirmed" }
+    );
+
+    // Authorities were revoked at launch, the event reports their final state
+    const { meta } = (await program.provider.connection.getTransaction(
+      signature,
+      { commitment: "confirmed", maxSupportedTransactionVersion: 0 }
+    ))!;
+    const [migrateEvent] = [
+      ...new EventParser(
+        program.programId,
+        new BorshCoder(program.idl)
+      ).parseLogs(meta!.logMessages!),
+    ].filter((event) => event.name === "MigrateEvent");
+
+    expect(migrateEvent.data.mintAuthorityRevoked).equal(
+      true,
+      "Mint authority revoked at launch not reported"
+    );
+    expect(migrateEvent.data.freezeAuthorityRevoked).equal(
+      true,
+      "Freeze authority revoked at launch not reported"
+    );
+
+    await expectError(
+      (
+        await updateMetadata(program, token2022Mint, payer, {
+          name: "Migrated",
+          symbol: null,
+          uri: null,
+        })
+      ).rpc(),
+      "AlreadyMigrated"
+    );
+
+    const metadata = await getTokenMetadata(
+      program.provider.connection,
+      token2022Mint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expect(metadata!.updateAuthority).equal(
+      undefined,
+      "Metadata update authority not revoked"
+    );
+    expect(metadata!.name).equal(name, "Metadata changed by migration");
   });
 });